@@ -0,0 +1,15 @@
+-- Recurring chores
+--
+-- A chore with a recurrence rule is a "series". The background loop spawns a
+-- regular chore row for each occurrence, linked back through series_id. Past
+-- occurrences are kept as history even if the series is deleted.
+ALTER TABLE chores ADD COLUMN recurrence TEXT; -- daily, weekly, every_n_days, monthly
+ALTER TABLE chores ADD COLUMN recurrence_weekdays TEXT; -- e.g. 'mon,wed,fri' for weekly
+ALTER TABLE chores ADD COLUMN recurrence_interval INTEGER; -- N for every_n_days
+ALTER TABLE chores ADD COLUMN recurrence_day INTEGER; -- day of month for monthly
+ALTER TABLE chores ADD COLUMN recurrence_start TEXT; -- anchor date for the schedule
+ALTER TABLE chores ADD COLUMN series_id BLOB REFERENCES chores(id) ON DELETE SET NULL;
+ALTER TABLE chores ADD COLUMN occurrence_date TEXT;
+
+CREATE INDEX idx_chores_series_id ON chores(series_id);
+CREATE UNIQUE INDEX idx_chores_series_occurrence ON chores(series_id, occurrence_date);
@@ -0,0 +1,5 @@
+-- Chore rewards are paid into the allowance ledger on completion
+ALTER TABLE allowance_ledger ADD COLUMN chore_id BLOB REFERENCES chores(id) ON DELETE SET NULL;
+ALTER TABLE chores ADD COLUMN reward_entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_allowance_ledger_chore_id ON allowance_ledger(chore_id);
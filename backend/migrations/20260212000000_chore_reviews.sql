@@ -0,0 +1,7 @@
+-- Parent review of chores completed by children
+ALTER TABLE chores ADD COLUMN review_status TEXT; -- pending, approved, rejected
+ALTER TABLE chores ADD COLUMN reviewed_by BLOB REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE chores ADD COLUMN reviewed_at TEXT;
+ALTER TABLE chores ADD COLUMN review_note TEXT;
+
+CREATE INDEX idx_chores_review_status ON chores(review_status);
@@ -0,0 +1,10 @@
+-- Chore rotation across family members
+CREATE TABLE chore_rotation_members (
+    chore_id BLOB NOT NULL REFERENCES chores(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (chore_id, position)
+);
+
+ALTER TABLE chores ADD COLUMN rotation_period_days INTEGER; -- NULL when the chore does not rotate
+ALTER TABLE chores ADD COLUMN rotation_start TEXT;
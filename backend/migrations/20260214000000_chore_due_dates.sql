@@ -0,0 +1,7 @@
+-- Chore due dates with an optional time-of-day window
+ALTER TABLE chores ADD COLUMN due_date TEXT;
+ALTER TABLE chores ADD COLUMN window_start TEXT; -- e.g. 06:30
+ALTER TABLE chores ADD COLUMN window_end TEXT; -- chore is late after this time on the due date
+ALTER TABLE chores ADD COLUMN window_label TEXT; -- e.g. 'before school'
+
+CREATE INDEX idx_chores_due_date ON chores(due_date);
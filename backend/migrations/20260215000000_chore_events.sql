@@ -0,0 +1,14 @@
+-- CHORE EVENTS (completion history)
+CREATE TABLE chore_events (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT, -- Use for deterministic ordering
+    id BLOB NOT NULL UNIQUE,
+    chore_id BLOB REFERENCES chores(id) ON DELETE SET NULL,
+    user_id BLOB REFERENCES users(id) ON DELETE SET NULL, -- assignee the event counts for
+    actor_id BLOB REFERENCES users(id) ON DELETE SET NULL, -- who made the change
+    event_type TEXT NOT NULL, -- completed, uncompleted
+    on_time INTEGER, -- NULL when the chore had no due date
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_chore_events_chore_id ON chore_events(chore_id);
+CREATE INDEX idx_chore_events_user_id ON chore_events(user_id, created_at);
@@ -0,0 +1,7 @@
+-- Open chores that any family member can claim
+ALTER TABLE chores ADD COLUMN claimable INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE chores ADD COLUMN claim_hours INTEGER; -- NULL means claims never expire
+ALTER TABLE chores ADD COLUMN claimed_at TEXT;
+ALTER TABLE chores ADD COLUMN claim_expires_at TEXT;
+
+CREATE INDEX idx_chores_claimable ON chores(claimable);
@@ -0,0 +1,50 @@
+-- POINTS LEDGER (earned from chores, spent in the rewards catalog)
+CREATE TABLE points_ledger (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT, -- Use for deterministic ordering
+    id BLOB NOT NULL UNIQUE,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL,
+    balance INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    chore_id BLOB REFERENCES chores(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_points_ledger_user_id ON points_ledger(user_id);
+
+ALTER TABLE chores ADD COLUMN points INTEGER;
+ALTER TABLE chores ADD COLUMN points_entry_id BLOB REFERENCES points_ledger(id) ON DELETE SET NULL;
+
+-- REWARDS CATALOG
+CREATE TABLE rewards (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    cost INTEGER NOT NULL, -- in points
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- REWARD REDEMPTIONS (requests approved by parents)
+CREATE TABLE reward_redemptions (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reward_id BLOB REFERENCES rewards(id) ON DELETE SET NULL,
+    reward_name TEXT NOT NULL, -- kept in case the reward is deleted
+    cost INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, approved, denied
+    note TEXT,
+    reviewed_by BLOB REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TEXT,
+    points_entry_id BLOB REFERENCES points_ledger(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_reward_redemptions_user_id ON reward_redemptions(user_id);
+CREATE INDEX idx_reward_redemptions_status ON reward_redemptions(status);
+
+CREATE TRIGGER update_rewards_updated_at AFTER UPDATE ON rewards
+BEGIN
+    UPDATE rewards SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
@@ -0,0 +1,45 @@
+-- ROUTINES (ordered daily checklists per family member)
+CREATE TABLE routines (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    window_start TEXT, -- time of day the routine becomes active
+    window_end TEXT,
+    reward INTEGER, -- Store as cents, paid when every step is done
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_routines_user_id ON routines(user_id);
+
+CREATE TABLE routine_steps (
+    id BLOB PRIMARY KEY,
+    routine_id BLOB NOT NULL REFERENCES routines(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    description TEXT NOT NULL
+);
+
+CREATE INDEX idx_routine_steps_routine_id ON routine_steps(routine_id);
+
+-- Checks are kept per day, so progress starts fresh every morning
+CREATE TABLE routine_step_checks (
+    step_id BLOB NOT NULL REFERENCES routine_steps(id) ON DELETE CASCADE,
+    check_date TEXT NOT NULL,
+    checked_by BLOB REFERENCES users(id) ON DELETE SET NULL, -- NULL when checked on a display
+    checked_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (step_id, check_date)
+);
+
+CREATE TABLE routine_completions (
+    routine_id BLOB NOT NULL REFERENCES routines(id) ON DELETE CASCADE,
+    completion_date TEXT NOT NULL,
+    reward_entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL,
+    completed_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (routine_id, completion_date)
+);
+
+CREATE TRIGGER update_routines_updated_at AFTER UPDATE ON routines
+BEGIN
+    UPDATE routines SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
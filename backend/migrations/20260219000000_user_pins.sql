@@ -0,0 +1,2 @@
+-- Short per-user PIN for checking off chores on a display
+ALTER TABLE users ADD COLUMN pin_hash TEXT;
@@ -0,0 +1,23 @@
+-- CHORE TEMPLATES (reusable chore definitions that can be handed out to several people)
+CREATE TABLE chore_templates (
+    id BLOB PRIMARY KEY,
+    description TEXT NOT NULL,
+    reward INTEGER, -- Store as cents
+    points INTEGER,
+    recurrence TEXT, -- default recurrence for chores created from the template
+    recurrence_weekdays TEXT,
+    recurrence_interval INTEGER,
+    recurrence_day INTEGER,
+    window_start TEXT,
+    window_end TEXT,
+    window_label TEXT,
+    min_age INTEGER, -- age suitability in years, inclusive
+    max_age INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TRIGGER update_chore_templates_updated_at AFTER UPDATE ON chore_templates
+BEGIN
+    UPDATE chore_templates SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
@@ -0,0 +1,6 @@
+-- Optional penalty deducted from allowance when a due chore is missed
+ALTER TABLE chores ADD COLUMN penalty INTEGER; -- Store as cents
+ALTER TABLE chores ADD COLUMN penalty_grace_minutes INTEGER; -- extra time after the deadline
+ALTER TABLE chores ADD COLUMN penalty_entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL;
+ALTER TABLE chores ADD COLUMN penalty_waived_by BLOB REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE chores ADD COLUMN penalty_waived_at TEXT;
@@ -0,0 +1,3 @@
+-- Photo a child attaches when completing a chore, stored under photos_dir/chore_proofs
+ALTER TABLE chores ADD COLUMN proof_photo TEXT; -- filename only
+ALTER TABLE chores ADD COLUMN proof_uploaded_at TEXT;
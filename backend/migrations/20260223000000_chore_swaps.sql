@@ -0,0 +1,24 @@
+-- CHORE SWAPS (one family member offering to trade chores with another)
+CREATE TABLE chore_swaps (
+    id BLOB PRIMARY KEY,
+    proposer_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    proposer_chore_id BLOB NOT NULL REFERENCES chores(id) ON DELETE CASCADE,
+    recipient_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recipient_chore_id BLOB NOT NULL REFERENCES chores(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, accepted, completed, declined, rejected, cancelled
+    needs_approval INTEGER NOT NULL DEFAULT 0, -- a parent must confirm once accepted
+    note TEXT,
+    responded_at TEXT,
+    reviewed_by BLOB REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_chore_swaps_proposer_id ON chore_swaps(proposer_id);
+CREATE INDEX idx_chore_swaps_recipient_id ON chore_swaps(recipient_id);
+
+CREATE TRIGGER update_chore_swaps_updated_at AFTER UPDATE ON chore_swaps
+BEGIN
+    UPDATE chore_swaps SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
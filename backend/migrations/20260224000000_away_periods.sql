@@ -0,0 +1,11 @@
+-- AWAY PERIODS (vacation, camp; chores and scheduled allowance pause while away)
+CREATE TABLE away_periods (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL, -- inclusive
+    note TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_away_periods_user_id ON away_periods(user_id, start_date);
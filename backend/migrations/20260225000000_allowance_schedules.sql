@@ -0,0 +1,32 @@
+-- ALLOWANCE SCHEDULES (recurring allowance paid by the background loop)
+CREATE TABLE allowance_schedules (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL, -- Store as cents
+    frequency TEXT NOT NULL, -- weekly, monthly
+    weekday TEXT, -- for weekly schedules, e.g. "sat"
+    month_day INTEGER, -- for monthly schedules, 1-31
+    start_date TEXT NOT NULL,
+    description TEXT,
+    catch_up INTEGER NOT NULL DEFAULT 0, -- pay periods missed while the server was down
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_allowance_schedules_user_id ON allowance_schedules(user_id);
+
+-- One row per schedule and pay date, so a period is never paid twice
+CREATE TABLE allowance_payouts (
+    schedule_id BLOB NOT NULL REFERENCES allowance_schedules(id) ON DELETE CASCADE,
+    period_date TEXT NOT NULL,
+    entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL, -- NULL when skipped
+    skipped INTEGER NOT NULL DEFAULT 0, -- the user was away
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (schedule_id, period_date)
+);
+
+CREATE TRIGGER update_allowance_schedules_updated_at AFTER UPDATE ON allowance_schedules
+BEGIN
+    UPDATE allowance_schedules SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
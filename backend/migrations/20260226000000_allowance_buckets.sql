@@ -0,0 +1,27 @@
+-- ALLOWANCE BUCKETS (spend/save/give jars within a user's allowance)
+CREATE TABLE allowance_buckets (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    split_percent INTEGER NOT NULL DEFAULT 0, -- share of incoming money
+    position INTEGER NOT NULL DEFAULT 0, -- the first bucket takes debits and rounding
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, name)
+);
+
+-- How each ledger entry was spread across the user's buckets. Money from
+-- before buckets existed stays unallocated until moved into one.
+CREATE TABLE allowance_allocations (
+    entry_id BLOB NOT NULL REFERENCES allowance_ledger(id) ON DELETE CASCADE,
+    bucket_id BLOB NOT NULL REFERENCES allowance_buckets(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL, -- Store as cents
+    PRIMARY KEY (entry_id, bucket_id)
+);
+
+CREATE INDEX idx_allowance_allocations_bucket_id ON allowance_allocations(bucket_id);
+
+CREATE TRIGGER update_allowance_buckets_updated_at AFTER UPDATE ON allowance_buckets
+BEGIN
+    UPDATE allowance_buckets SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
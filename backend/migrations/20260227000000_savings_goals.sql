@@ -0,0 +1,20 @@
+-- SAVINGS GOALS (something a family member is saving up for)
+CREATE TABLE savings_goals (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    bucket_id BLOB REFERENCES allowance_buckets(id) ON DELETE SET NULL, -- NULL tracks the whole balance
+    name TEXT NOT NULL,
+    target_amount INTEGER NOT NULL, -- Store as cents
+    image_url TEXT,
+    target_date TEXT,
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_savings_goals_user_id ON savings_goals(user_id);
+
+CREATE TRIGGER update_savings_goals_updated_at AFTER UPDATE ON savings_goals
+BEGIN
+    UPDATE savings_goals SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
@@ -0,0 +1,28 @@
+-- INTEREST RATES (paid monthly on a user's balance or on one bucket)
+CREATE TABLE interest_rates (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    bucket_id BLOB REFERENCES allowance_buckets(id) ON DELETE CASCADE, -- NULL earns on the whole balance
+    annual_rate TEXT NOT NULL, -- Percent as an exact decimal, e.g. '5.25'
+    active INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_interest_rates_user_id ON interest_rates(user_id);
+
+CREATE TRIGGER update_interest_rates_updated_at AFTER UPDATE ON interest_rates
+BEGIN
+    UPDATE interest_rates SET updated_at = datetime('now') WHERE id = OLD.id;
+END;
+
+-- One row per rate and month, so interest is never paid twice
+CREATE TABLE interest_payouts (
+    rate_id BLOB NOT NULL REFERENCES interest_rates(id) ON DELETE CASCADE,
+    period_date TEXT NOT NULL, -- First day of the month paid
+    entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL,
+    balance INTEGER NOT NULL, -- Balance interest was calculated on
+    amount INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (rate_id, period_date)
+);
@@ -0,0 +1,6 @@
+-- Voiding appends a reversing entry and flags the original; history is never rewritten
+ALTER TABLE allowance_ledger ADD COLUMN reversal_of BLOB REFERENCES allowance_ledger(id);
+ALTER TABLE allowance_ledger ADD COLUMN voided_at TEXT;
+ALTER TABLE allowance_ledger ADD COLUMN voided_by BLOB REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_allowance_ledger_reversal_of ON allowance_ledger(reversal_of);
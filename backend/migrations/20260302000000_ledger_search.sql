@@ -0,0 +1,26 @@
+-- Full-text search over allowance descriptions, kept in step with the ledger by triggers
+CREATE VIRTUAL TABLE allowance_ledger_fts USING fts5(
+    description,
+    content = 'allowance_ledger',
+    content_rowid = 'seq'
+);
+
+INSERT INTO allowance_ledger_fts (allowance_ledger_fts) VALUES ('rebuild');
+
+CREATE TRIGGER allowance_ledger_fts_insert AFTER INSERT ON allowance_ledger
+BEGIN
+    INSERT INTO allowance_ledger_fts (rowid, description) VALUES (NEW.seq, NEW.description);
+END;
+
+CREATE TRIGGER allowance_ledger_fts_delete AFTER DELETE ON allowance_ledger
+BEGIN
+    INSERT INTO allowance_ledger_fts (allowance_ledger_fts, rowid, description) VALUES ('delete', OLD.seq, OLD.description);
+END;
+
+CREATE TRIGGER allowance_ledger_fts_update AFTER UPDATE OF description ON allowance_ledger
+BEGIN
+    INSERT INTO allowance_ledger_fts (allowance_ledger_fts, rowid, description) VALUES ('delete', OLD.seq, OLD.description);
+    INSERT INTO allowance_ledger_fts (rowid, description) VALUES (NEW.seq, NEW.description);
+END;
+
+CREATE INDEX idx_allowance_ledger_user_seq ON allowance_ledger(user_id, seq);
@@ -0,0 +1,17 @@
+-- SPENDING REQUESTS (a child asks to spend allowance, a parent approves or denies)
+CREATE TABLE spending_requests (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    bucket_id BLOB REFERENCES allowance_buckets(id) ON DELETE SET NULL, -- NULL spends from the whole balance
+    amount INTEGER NOT NULL, -- Store as cents
+    description TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, approved, denied, cancelled
+    note TEXT,
+    reviewed_by BLOB REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TEXT,
+    entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_spending_requests_user_id ON spending_requests(user_id);
+CREATE INDEX idx_spending_requests_status ON spending_requests(status);
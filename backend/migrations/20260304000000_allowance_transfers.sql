@@ -0,0 +1,18 @@
+-- TRANSFERS (money moved from one family member's allowance to another's)
+CREATE TABLE allowance_transfers (
+    id BLOB PRIMARY KEY,
+    from_user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    to_user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL, -- Store as cents
+    description TEXT,
+    debit_entry_id BLOB NOT NULL REFERENCES allowance_ledger(id),
+    credit_entry_id BLOB NOT NULL REFERENCES allowance_ledger(id),
+    created_by BLOB REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_allowance_transfers_from_user_id ON allowance_transfers(from_user_id);
+CREATE INDEX idx_allowance_transfers_to_user_id ON allowance_transfers(to_user_id);
+
+-- Both sides of a transfer point back at it; deferred since the entries are posted first
+ALTER TABLE allowance_ledger ADD COLUMN transfer_id BLOB REFERENCES allowance_transfers(id) DEFERRABLE INITIALLY DEFERRED;
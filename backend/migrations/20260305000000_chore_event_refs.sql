@@ -0,0 +1,5 @@
+-- Chore an event was for, kept after the chore is deleted so its history still counts.
+-- Events whose chore was deleted before this column existed are left without one.
+ALTER TABLE chore_events ADD COLUMN chore_ref BLOB;
+
+UPDATE chore_events SET chore_ref = chore_id;
@@ -0,0 +1,29 @@
+-- Deleting either person keeps the transfer record for the other, with their side cleared.
+-- SQLite can't change a column's constraints in place, so the table is rebuilt.
+CREATE TABLE allowance_transfers_old AS SELECT * FROM allowance_transfers;
+
+DROP TABLE allowance_transfers;
+
+CREATE TABLE allowance_transfers (
+    id BLOB PRIMARY KEY,
+    from_user_id BLOB REFERENCES users(id) ON DELETE SET NULL,
+    to_user_id BLOB REFERENCES users(id) ON DELETE SET NULL,
+    amount INTEGER NOT NULL, -- Store as cents
+    description TEXT,
+    debit_entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL,
+    credit_entry_id BLOB REFERENCES allowance_ledger(id) ON DELETE SET NULL,
+    created_by BLOB REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Puts back the rows the ledger's deferred transfer_id check is waiting on
+INSERT INTO allowance_transfers (id, from_user_id, to_user_id, amount, description,
+                                 debit_entry_id, credit_entry_id, created_by, created_at)
+SELECT id, from_user_id, to_user_id, amount, description,
+       debit_entry_id, credit_entry_id, created_by, created_at
+FROM allowance_transfers_old;
+
+DROP TABLE allowance_transfers_old;
+
+CREATE INDEX idx_allowance_transfers_from_user_id ON allowance_transfers(from_user_id);
+CREATE INDEX idx_allowance_transfers_to_user_id ON allowance_transfers(to_user_id);
@@ -1,8 +1,25 @@
 use std::{sync::Arc, time::Duration};
 
+use chrono::{Datelike, Local, NaiveDate};
 use sqlx::query_as;
-
-use crate::{error::AppError, models::calendar::Calendar, state::AppState, utils::google_oauth};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    handlers::away::load_away_periods,
+    models::{
+        allowance::{monthly_interest, AllowanceSchedule, InterestRate},
+        away::is_away,
+        calendar::Calendar,
+        chore::Chore,
+    },
+    state::AppState,
+    utils::{
+        google_oauth,
+        ledger::{allowance_balance, post_entry, NewLedgerEntry},
+        photo_files::{photo_path, CHORE_PROOFS_DIR, DEFAULT_PROOF_RETENTION_DAYS},
+    },
+};
 
 const DEFAULT_REFRESH_SECONDS: u64 = 60 * 60;
 
@@ -49,6 +66,461 @@ async fn refresh_all(state: &AppState) -> Result<(), AppError> {
         tracing::warn!(error = ?e, "calendar refresh failed");
     }
 
+    if let Err(e) = expire_chore_claims(state).await {
+        tracing::warn!(error = ?e, "chore claim expiry failed");
+    }
+
+    if let Err(e) = advance_chore_rotations(state).await {
+        tracing::warn!(error = ?e, "chore rotation failed");
+    }
+
+    if let Err(e) = generate_chore_occurrences(state).await {
+        tracing::warn!(error = ?e, "chore generation failed");
+    }
+
+    if let Err(e) = apply_chore_penalties(state).await {
+        tracing::warn!(error = ?e, "chore penalties failed");
+    }
+
+    if let Err(e) = prune_chore_proofs(state).await {
+        tracing::warn!(error = ?e, "chore proof pruning failed");
+    }
+
+    if let Err(e) = run_allowance_schedules(state).await {
+        tracing::warn!(error = ?e, "scheduled allowance failed");
+    }
+
+    if let Err(e) = apply_interest(state).await {
+        tracing::warn!(error = ?e, "interest payout failed");
+    }
+
+    Ok(())
+}
+
+/// Put claimed chores back on the open board once their claim runs out
+async fn expire_chore_claims(state: &AppState) -> Result<(), AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE chores
+        SET assigned_to = NULL, claimed_at = NULL, claim_expires_at = NULL, review_status = NULL,
+            reviewed_by = NULL, reviewed_at = NULL, review_note = NULL, updated_at = datetime('now')
+        WHERE claimable = 1 AND completed = 0
+          AND claim_expires_at IS NOT NULL AND claim_expires_at < datetime('now')
+          AND (review_status IS NULL OR review_status != 'pending')
+        "#,
+    )
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() > 0 {
+        tracing::info!(count = result.rows_affected(), "Released expired chore claims");
+    }
+
+    Ok(())
+}
+
+/// Hand each rotating chore to whoever's turn it is today.
+///
+/// Runs before occurrence generation so new occurrences pick up the current assignee.
+async fn advance_chore_rotations(state: &AppState) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+
+    // Chores waiting for review or claimed stay with whoever did or took them
+    let chores = query_as::<_, Chore>(
+        r#"
+        SELECT * FROM chores
+        WHERE rotation_period_days IS NOT NULL AND completed = 0
+          AND (review_status IS NULL OR review_status != 'pending') AND claimed_at IS NULL
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for chore in chores {
+        let members: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT user_id FROM chore_rotation_members WHERE chore_id = $1 ORDER BY position ASC",
+        )
+        .bind(chore.id)
+        .fetch_all(&state.db)
+        .await?;
+
+        let Some(slot) = chore.rotation_slot(today, members.len()) else {
+            continue;
+        };
+
+        if chore.assigned_to != Some(members[slot]) {
+            let result = sqlx::query(
+                r#"
+                UPDATE chores SET assigned_to = $1, updated_at = datetime('now')
+                WHERE id = $2 AND completed = 0
+                  AND (review_status IS NULL OR review_status != 'pending') AND claimed_at IS NULL
+                "#,
+            )
+            .bind(members[slot])
+            .bind(chore.id)
+            .execute(&state.db)
+            .await?;
+
+            // Done or sent for review since it was read
+            if result.rows_affected() == 0 {
+                continue;
+            }
+
+            tracing::info!(chore_id = %chore.id, user_id = %members[slot], "Rotated chore assignee");
+        }
+    }
+
+    Ok(())
+}
+
+/// Spawn today's occurrence of every recurring chore series.
+///
+/// Occurrences are unique per series and date, so running this repeatedly is
+/// harmless. Earlier occurrences are left untouched as history.
+pub async fn generate_chore_occurrences(state: &AppState) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+
+    let series = query_as::<_, Chore>(
+        "SELECT * FROM chores WHERE recurrence IS NOT NULL",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut conn = state.db.acquire().await?;
+    let away = load_away_periods(&mut conn).await?;
+    drop(conn);
+
+    let mut spawned = 0;
+
+    // Nothing is generated for someone who is away; open chores still are
+    for chore in series
+        .iter()
+        .filter(|c| c.occurs_on(today))
+        .filter(|c| c.assigned_to.is_none_or(|user_id| !is_away(&away, user_id, today)))
+    {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO chores (id, description, assigned_to, reward, series_id, occurrence_date,
+                                due_date, window_start, window_end, window_label, claimable, claim_hours,
+                                points, penalty, penalty_grace_minutes)
+            VALUES ($1, $2, $3, $4, $5, $6, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&chore.description)
+        .bind(chore.assigned_to)
+        .bind(chore.reward)
+        .bind(chore.id)
+        .bind(today)
+        .bind(chore.window_start)
+        .bind(chore.window_end)
+        .bind(&chore.window_label)
+        .bind(chore.claimable)
+        .bind(chore.claim_hours)
+        .bind(chore.points)
+        .bind(chore.penalty)
+        .bind(chore.penalty_grace_minutes)
+        .execute(&state.db)
+        .await?;
+
+        spawned += result.rows_affected();
+    }
+
+    if spawned > 0 {
+        tracing::info!(count = spawned, "Spawned recurring chore occurrences");
+    }
+
+    Ok(())
+}
+
+/// Charge the penalty on chores still not done once their grace period is over.
+///
+/// Each chore is charged at most once; the ledger entry is remembered on the chore.
+async fn apply_chore_penalties(state: &AppState) -> Result<(), AppError> {
+    let now = Local::now().naive_local();
+
+    let chores = query_as::<_, Chore>(
+        r#"
+        SELECT * FROM chores
+        WHERE penalty IS NOT NULL AND penalty_entry_id IS NULL AND completed = 0
+          AND recurrence IS NULL AND assigned_to IS NOT NULL AND due_date IS NOT NULL
+          AND (review_status IS NULL OR review_status != 'pending')
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut conn = state.db.acquire().await?;
+    let away = load_away_periods(&mut conn).await?;
+    drop(conn);
+
+    for chore in chores.iter().filter(|c| c.penalty_deadline().is_some_and(|due| now > due)) {
+        let (Some(user_id), Some(penalty)) = (chore.assigned_to, chore.penalty) else {
+            continue;
+        };
+
+        // No penalty for a chore missed while away; one that came due
+        // before leaving waits until they're back
+        if chore.due_date.is_some_and(|due| is_away(&away, user_id, due))
+            || is_away(&away, user_id, now.date())
+        {
+            continue;
+        }
+
+        let mut tx = state.db.begin().await?;
+
+        let entry = post_entry(&mut tx, NewLedgerEntry {
+            user_id,
+            amount: -penalty,
+            description: format!("Missed chore: {}", chore.description),
+            chore_id: Some(chore.id),
+            ..Default::default()
+        }).await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE chores SET penalty_entry_id = $1, updated_at = datetime('now')
+            WHERE id = $2 AND penalty_entry_id IS NULL
+            "#,
+        )
+        .bind(entry.id)
+        .bind(chore.id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Someone else got here first; dropping the transaction discards the entry
+        if result.rows_affected() == 0 {
+            continue;
+        }
+
+        tx.commit().await?;
+
+        tracing::info!(chore_id = %chore.id, user_id = %user_id, amount = penalty, "Charged missed chore penalty");
+    }
+
+    Ok(())
+}
+
+/// Delete chore proof photos once they are older than the retention period.
+///
+/// Files no longer referenced by any chore (for example after the chore was
+/// deleted) are swept up by their modification time.
+async fn prune_chore_proofs(state: &AppState) -> Result<(), AppError> {
+    let retention: Option<String> = sqlx::query_scalar(
+        "SELECT value FROM settings WHERE key = 'chore_proof_retention_days'",
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    let days = retention
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_PROOF_RETENTION_DAYS)
+        .max(1);
+
+    let dir = state.photos_dir.join(CHORE_PROOFS_DIR);
+
+    let expired: Vec<(Uuid, String)> = sqlx::query_as(
+        r#"
+        SELECT id, proof_photo FROM chores
+        WHERE proof_photo IS NOT NULL AND proof_uploaded_at < datetime('now', '-' || $1 || ' days')
+        "#,
+    )
+    .bind(days)
+    .fetch_all(&state.db)
+    .await?;
+
+    for (chore_id, filename) in &expired {
+        if let Ok(path) = photo_path(&dir, filename) {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+
+        sqlx::query(
+            "UPDATE chores SET proof_photo = NULL, proof_uploaded_at = NULL WHERE id = $1 AND proof_photo = $2",
+        )
+        .bind(chore_id)
+        .bind(filename)
+        .execute(&state.db)
+        .await?;
+    }
+
+    if !expired.is_empty() {
+        tracing::info!(count = expired.len(), "Pruned expired chore proof photos");
+    }
+
+    let cutoff = std::time::SystemTime::now() - Duration::from_secs(days as u64 * 24 * 60 * 60);
+    if let Ok(mut entries) = tokio::fs::read_dir(&dir).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let stale = entry
+                .metadata()
+                .await
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified < cutoff);
+
+            if stale {
+                let _ = tokio::fs::remove_file(entry.path()).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// How far back catch-up mode looks for missed allowance periods
+const MAX_CATCH_UP_DAYS: i64 = 366;
+
+/// Pay scheduled allowance for every period that has come due.
+///
+/// Each pay date is claimed in `allowance_payouts` in the same transaction as
+/// its ledger entry, so restarts and overlapping runs never pay a period twice.
+/// Schedules in catch-up mode also pay dates missed while the server was down.
+pub async fn run_allowance_schedules(state: &AppState) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+
+    let schedules = query_as::<_, AllowanceSchedule>(
+        "SELECT * FROM allowance_schedules WHERE active = 1",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut conn = state.db.acquire().await?;
+    let away = load_away_periods(&mut conn).await?;
+    drop(conn);
+
+    for schedule in schedules {
+        let dates = if schedule.catch_up {
+            let last_paid: Option<NaiveDate> = sqlx::query_scalar(
+                "SELECT MAX(period_date) FROM allowance_payouts WHERE schedule_id = $1",
+            )
+            .bind(schedule.id)
+            .fetch_one(&state.db)
+            .await?;
+
+            let from = last_paid
+                .and_then(|d| d.succ_opt())
+                .unwrap_or(schedule.start_date)
+                .max(today - chrono::Duration::days(MAX_CATCH_UP_DAYS));
+            schedule.pay_dates(from, today)
+        } else if schedule.pays_on(today) {
+            vec![today]
+        } else {
+            vec![]
+        };
+
+        for date in dates {
+            // Periods spent away are recorded as skipped so they aren't paid later
+            let skipped = is_away(&away, schedule.user_id, date);
+
+            let mut tx = state.db.begin().await?;
+
+            let claimed = sqlx::query(
+                r#"
+                INSERT INTO allowance_payouts (schedule_id, period_date, skipped)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(schedule.id)
+            .bind(date)
+            .bind(skipped)
+            .execute(&mut *tx)
+            .await?;
+
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            if !skipped {
+                let description = schedule.description.as_deref().unwrap_or("Allowance");
+                let entry = post_entry(&mut tx, NewLedgerEntry {
+                    user_id: schedule.user_id,
+                    amount: schedule.amount,
+                    description: format!("{} ({})", description, date),
+                    ..Default::default()
+                }).await?;
+
+                sqlx::query(
+                    "UPDATE allowance_payouts SET entry_id = $1 WHERE schedule_id = $2 AND period_date = $3",
+                )
+                .bind(entry.id)
+                .bind(schedule.id)
+                .bind(date)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            tx.commit().await?;
+
+            tracing::info!(schedule_id = %schedule.id, user_id = %schedule.user_id, %date, skipped, "Processed scheduled allowance");
+        }
+    }
+
+    Ok(())
+}
+
+/// Pay this month's interest on every active rate.
+///
+/// Interest is worked out on the balance at the first run of the month and
+/// claimed per rate and month, so it's paid once even across restarts.
+/// Bucket rates credit that bucket; whole-balance rates split like any income.
+async fn apply_interest(state: &AppState) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+    let period = today.with_day(1).unwrap_or(today);
+
+    let rates = query_as::<_, InterestRate>(
+        "SELECT * FROM interest_rates WHERE active = 1",
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for rate in rates {
+        let mut tx = state.db.begin().await?;
+
+        let balance = allowance_balance(&mut tx, rate.user_id, rate.bucket_id).await?;
+        let amount = monthly_interest(balance, rate.rate());
+
+        let claimed = sqlx::query(
+            r#"
+            INSERT INTO interest_payouts (rate_id, period_date, balance, amount)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(rate.id)
+        .bind(period)
+        .bind(balance)
+        .bind(amount)
+        .execute(&mut *tx)
+        .await?;
+
+        if claimed.rows_affected() == 0 {
+            continue;
+        }
+
+        if amount != 0 {
+            let entry = post_entry(&mut tx, NewLedgerEntry {
+                user_id: rate.user_id,
+                amount,
+                description: format!("Interest at {}% ({})", rate.annual_rate, period.format("%B %Y")),
+                bucket_id: rate.bucket_id,
+                ..Default::default()
+            }).await?;
+
+            sqlx::query(
+                "UPDATE interest_payouts SET entry_id = $1 WHERE rate_id = $2 AND period_date = $3",
+            )
+            .bind(entry.id)
+            .bind(rate.id)
+            .bind(period)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        tracing::info!(rate_id = %rate.id, user_id = %rate.user_id, balance, amount, "Paid monthly interest");
+    }
+
     Ok(())
 }
 
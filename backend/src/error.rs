@@ -15,6 +15,7 @@ pub enum AppError {
     UserNotFound,
     InvalidInput(String),
     BadRequest(String),
+    TooManyAttempts,
 }
 
 impl From<sqlx::Error> for AppError {
@@ -55,6 +56,7 @@ impl IntoResponse for AppError {
             AppError::UserNotFound => (StatusCode::NOT_FOUND, "User not found".to_string()),
             AppError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::TooManyAttempts => (StatusCode::TOO_MANY_REQUESTS, "Too many attempts, try again later".to_string()),
         };
 
         let body = Json(json!({
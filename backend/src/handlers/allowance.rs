@@ -1,16 +1,41 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use std::sync::Arc;
+use chrono::{Local, Weekday};
+use rust_decimal::Decimal;
 use sqlx::query_as;
 use uuid::Uuid;
 
 use crate::{
+    background,
     error::AppError,
-    models::user::{AllowanceTransaction, CreateTransactionSchema, UserBalance},
+    models::{
+        allowance::{
+            fts_query, project_interest, AllowanceBucket, AllowancePayout, AllowanceSchedule,
+            AllowanceTransfer, BucketBalance, BucketTransferSchema, CreateBucketSchema,
+            CreateInterestSchema, CreateScheduleSchema, CreateTransferSchema, ExportFormat,
+            ExportLedgerQuery, InterestPayout, InterestPreview, InterestPreviewQuery, InterestRate,
+            LedgerCheck, LedgerPage, LedgerQuery, LedgerSummary, PayoutFrequency, UpdateBucketSchema,
+            UpdateInterestSchema, UpdateScheduleSchema, VerifyLedgerSchema,
+        },
+        user::{
+            AllowanceTransaction, CreateTransactionSchema, UserBalance, VoidTransactionSchema,
+            VoidedTransaction,
+        },
+    },
     state::AppState,
-    utils::auth_helpers::require_admin,
+    utils::{
+        auth_helpers::require_admin,
+        ledger::{
+            self, allowance_balance, bucket_balances, check_overdraft, post_entry,
+            transfer_between_buckets, user_buckets, with_buckets, NewLedgerEntry, MAX_REQUEST_AMOUNT,
+        },
+        ledger_export::{to_csv, to_ofx, to_qif, Currency},
+    },
     middleware::auth::AuthUser,
 };
 
@@ -38,45 +63,144 @@ pub async fn add_transaction(
         return Err(AppError::InvalidInput("Description too long".to_string()));
     }
 
+    if payload.amount.unsigned_abs() > MAX_REQUEST_AMOUNT as u64 {
+        return Err(AppError::InvalidInput("Amount must be at most 1,000,000.00 either way".to_string()));
+    }
+
     let mut tx = state.db.begin().await.map_err(AppError::Sqlx)?;
 
-    let latest_balance: Option<i64> = sqlx::query_scalar(
-        "SELECT balance FROM allowance_ledger WHERE user_id = $1 ORDER BY seq DESC LIMIT 1"
-    )
-        .bind(user_id)
+    let transaction = post_entry(&mut tx, NewLedgerEntry {
+        user_id,
+        amount: payload.amount,
+        description: payload.description,
+        bucket_id: payload.bucket_id,
+        ..Default::default()
+    }).await?;
+
+    tx.commit().await.map_err(AppError::Sqlx)?;
+
+    Ok(Json(transaction))
+}
+
+/// Cancel a mistaken entry by appending one that undoes it.
+///
+/// The original stays in the ledger flagged as voided, and the reversal is
+/// posted at the end so every running balance after it stays correct.
+pub async fn void_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<VoidTransactionSchema>,
+) -> Result<Json<VoidedTransaction>, AppError> {
+    require_admin(&auth)?;
+
+    if payload.reason.as_ref().is_some_and(|r| r.len() > 200) {
+        return Err(AppError::InvalidInput("Reason too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let original = query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger WHERE id = $1")
+        .bind(id)
         .fetch_optional(&mut *tx)
-        .await.map_err(AppError::Sqlx)?;
+        .await?
+        .ok_or(AppError::InvalidInput("Transaction not found".to_string()))?;
 
-    let current_balance = latest_balance.unwrap_or(0);
-    let new_balance = current_balance + payload.amount;
-    let id = Uuid::new_v4();
+    if original.voided_at.is_some() {
+        return Err(AppError::InvalidInput("Transaction is already voided".to_string()));
+    }
 
-    sqlx::query(
-        r#"
-        INSERT INTO allowance_ledger (id, user_id, amount, balance, description)
-        VALUES ($1, $2, $3, $4, $5)
-        "#
-    )
-    .bind(id)
-    .bind(user_id)
-    .bind(payload.amount)
-    .bind(new_balance)
-    .bind(payload.description)
-    .execute(&mut *tx)
-    .await.map_err(AppError::Sqlx)?;
+    if original.reversal_of.is_some() {
+        return Err(AppError::InvalidInput("Reversing entries can't be voided".to_string()));
+    }
+
+    if original.transfer_id.is_some() {
+        return Err(AppError::InvalidInput("Transfers can't be voided one side at a time".to_string()));
+    }
 
-    let transaction = query_as::<_, AllowanceTransaction>(
-        "SELECT * FROM allowance_ledger WHERE id = $1"
+    // Chore undos and waived penalties already reversed some entries
+    let reversed: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM allowance_ledger WHERE reversal_of = $1)"
     )
-    .bind(id)
-    .fetch_one(&mut *tx)
-    .await.map_err(AppError::Sqlx)?;
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
 
-    tx.commit().await.map_err(AppError::Sqlx)?;
+    if reversed {
+        return Err(AppError::InvalidInput("Transaction has already been reversed".to_string()));
+    }
 
-    Ok(Json(transaction))
+    let description = match payload.reason.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        Some(reason) => format!("Voided: {} ({})", original.description, reason),
+        None => format!("Voided: {}", original.description),
+    };
+
+    let reversal = post_entry(&mut tx, NewLedgerEntry {
+        user_id: original.user_id,
+        amount: -original.amount,
+        description,
+        chore_id: original.chore_id,
+        reverses: Some(original.id),
+        ..Default::default()
+    }).await?;
+
+    sqlx::query("UPDATE allowance_ledger SET voided_at = datetime('now'), voided_by = $1 WHERE id = $2")
+        .bind(auth.user_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    let voided = query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(entry_id = %id, reversal_id = %reversal.id, "Voided allowance transaction");
+
+    Ok(Json(VoidedTransaction { voided, reversal }))
 }
 
+/// Check every stored running balance, optionally fixing the ones that drifted
+pub async fn verify_ledger(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<VerifyLedgerSchema>,
+) -> Result<Json<LedgerCheck>, AppError> {
+    require_admin(&auth)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let check = ledger::verify_ledger(&mut tx, payload.repair.unwrap_or(false)).await?;
+
+    tx.commit().await?;
+
+    if !check.mismatches.is_empty() {
+        tracing::warn!(mismatches = check.mismatches.len(), repaired = check.repaired, "Allowance ledger balances drifted");
+    }
+
+    Ok(Json(check))
+}
+
+/// Largest page of ledger entries returned at once
+const MAX_LEDGER_PAGE: i64 = 200;
+const DEFAULT_LEDGER_PAGE: i64 = 50;
+
+/// Filters shared by a ledger page and its summary; binds $1 to $7
+const LEDGER_FILTER: &str = r#"
+    WHERE user_id = $1
+      AND ($2 IS NULL OR date(created_at, 'localtime') >= $2)
+      AND ($3 IS NULL OR date(created_at, 'localtime') <= $3)
+      AND ($4 IS NULL OR amount >= $4)
+      AND ($5 IS NULL OR amount <= $5)
+      AND ($6 IS NULL OR seq IN (SELECT rowid FROM allowance_ledger_fts WHERE allowance_ledger_fts MATCH $6))
+      AND ($7 IS NULL OR seq < $7)
+"#;
+
+/// A user's whole ledger, newest first.
+///
+/// Kept as it was for older clients; `get_ledger_page` pages and filters it.
 pub async fn get_ledger(
     State(state): State<Arc<AppState>>,
     Path(user_id): Path<Uuid>,
@@ -109,6 +233,169 @@ pub async fn get_ledger(
     Ok(Json(ledger))
 }
 
+pub async fn get_ledger_page(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<LedgerQuery>,
+    auth: AuthUser,
+) -> Result<Json<LedgerPage>, AppError> {
+    // Users can view their own ledger, admins can view anyone
+    if !auth.is_admin() && auth.user_id != user_id {
+        return Err(AppError::AuthError);
+    }
+
+    // Verify user exists
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    if query.search.as_ref().is_some_and(|s| s.len() > 200) {
+        return Err(AppError::InvalidInput("Search too long".to_string()));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_LEDGER_PAGE).clamp(1, MAX_LEDGER_PAGE);
+    let search = query.search.as_deref().and_then(fts_query);
+
+    // One extra row tells us whether there's another page
+    let mut entries = query_as::<_, AllowanceTransaction>(&format!(
+        "SELECT * FROM allowance_ledger {} ORDER BY seq DESC LIMIT $8",
+        LEDGER_FILTER
+    ))
+    .bind(user_id)
+    .bind(query.from)
+    .bind(query.to)
+    .bind(query.min_amount)
+    .bind(query.max_amount)
+    .bind(&search)
+    .bind(query.before)
+    .bind(limit + 1)
+    .fetch_all(&state.db)
+    .await?;
+
+    let next_cursor = if entries.len() as i64 > limit {
+        entries.truncate(limit as usize);
+        entries.last().map(|e| e.seq)
+    } else {
+        None
+    };
+
+    // The summary covers every page, so the cursor isn't applied
+    let summary = query_as::<_, LedgerSummary>(&format!(
+        r#"
+        SELECT COUNT(*) as total_count,
+               COALESCE(SUM(CASE WHEN amount > 0 THEN amount ELSE 0 END), 0) as credits,
+               COALESCE(SUM(CASE WHEN amount < 0 THEN amount ELSE 0 END), 0) as debits,
+               COALESCE(SUM(amount), 0) as net
+        FROM allowance_ledger {}
+        "#,
+        LEDGER_FILTER
+    ))
+    .bind(user_id)
+    .bind(query.from)
+    .bind(query.to)
+    .bind(query.min_amount)
+    .bind(query.max_amount)
+    .bind(&search)
+    .bind(None::<i64>)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(LedgerPage { entries, next_cursor, summary }))
+}
+
+/// Download a user's ledger for a date range as CSV, OFX or QIF
+pub async fn export_ledger(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<ExportLedgerQuery>,
+    auth: AuthUser,
+) -> Result<Response, AppError> {
+    // Users can export their own ledger, admins can export anyone's
+    if !auth.is_admin() && auth.user_id != user_id {
+        return Err(AppError::AuthError);
+    }
+
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(AppError::InvalidInput("Start date must be before end date".to_string()));
+    }
+
+    let mut conn = state.db.acquire().await?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(user_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    let entries = query_as::<_, AllowanceTransaction>(
+        r#"
+        SELECT * FROM allowance_ledger
+        WHERE user_id = $1
+          AND ($2 IS NULL OR date(created_at, 'localtime') >= $2)
+          AND ($3 IS NULL OR date(created_at, 'localtime') <= $3)
+        ORDER BY seq ASC
+        "#
+    )
+    .bind(user_id)
+    .bind(query.from)
+    .bind(query.to)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let currency = Currency::load(&mut conn).await?;
+
+    let today = Local::now().date_naive();
+    let to = query.to.unwrap_or(today);
+    let from = query
+        .from
+        .or_else(|| entries.first().map(|e| e.created_at.with_timezone(&Local).date_naive()))
+        .unwrap_or(to);
+
+    let body = match query.format {
+        ExportFormat::Csv => to_csv(&entries, &currency),
+        ExportFormat::Qif => to_qif(&entries, &currency),
+        ExportFormat::Ofx => {
+            let closing_balance: Option<i64> = sqlx::query_scalar(
+                r#"
+                SELECT balance FROM allowance_ledger
+                WHERE user_id = $1 AND date(created_at, 'localtime') <= $2
+                ORDER BY seq DESC LIMIT 1
+                "#
+            )
+            .bind(user_id)
+            .bind(to)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            to_ofx(&entries, &currency, &user_id.simple().to_string(), from, to, closing_balance.unwrap_or(0))
+        }
+    };
+
+    let filename = format!("allowance-{}-{}.{}", from, to, query.format.extension());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    ).into_response())
+}
+
 pub async fn get_balances(
     State(state): State<Arc<AppState>>,
     auth: AuthUser,
@@ -145,5 +432,862 @@ pub async fn get_balances(
         .await?
     };
 
+    let mut conn = state.db.acquire().await?;
+    let balances = with_buckets(&mut conn, balances).await?;
+
     Ok(Json(balances))
-}
\ No newline at end of file
+}
+
+/// Validate a schedule's amount, pay day and description
+fn validate_schedule(
+    amount: i64,
+    frequency: PayoutFrequency,
+    weekday: Option<&str>,
+    month_day: Option<i64>,
+    description: Option<&str>,
+) -> Result<(), AppError> {
+    if amount <= 0 || amount > MAX_REQUEST_AMOUNT {
+        return Err(AppError::InvalidInput("Amount must be positive and at most 1,000,000.00".to_string()));
+    }
+
+    match frequency {
+        PayoutFrequency::Weekly => {
+            if weekday.and_then(|w| w.trim().parse::<Weekday>().ok()).is_none() {
+                return Err(AppError::InvalidInput("Weekly allowance needs a weekday".to_string()));
+            }
+        }
+        PayoutFrequency::Monthly => {
+            if !matches!(month_day, Some(1..=31)) {
+                return Err(AppError::InvalidInput("Day of month must be between 1 and 31".to_string()));
+            }
+        }
+    }
+
+    if description.is_some_and(|d| d.len() > 200) {
+        return Err(AppError::InvalidInput("Description too long".to_string()));
+    }
+
+    Ok(())
+}
+
+pub async fn list_schedules(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AllowanceSchedule>>, AppError> {
+    // Users see their own schedules, admins see everyone's
+    let schedules = query_as::<_, AllowanceSchedule>(
+        "SELECT * FROM allowance_schedules WHERE $1 OR user_id = $2 ORDER BY created_at ASC"
+    )
+        .bind(auth.is_admin())
+        .bind(auth.user_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(schedules))
+}
+
+pub async fn create_schedule(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateScheduleSchema>,
+) -> Result<Json<AllowanceSchedule>, AppError> {
+    require_admin(&auth)?;
+
+    validate_schedule(
+        payload.amount,
+        payload.frequency,
+        payload.weekday.as_deref(),
+        payload.month_day,
+        payload.description.as_deref(),
+    )?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(payload.user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO allowance_schedules (id, user_id, amount, frequency, weekday, month_day, start_date,
+                                         description, catch_up)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#
+    )
+    .bind(id)
+    .bind(payload.user_id)
+    .bind(payload.amount)
+    .bind(payload.frequency)
+    .bind(payload.weekday.map(|w| w.trim().to_lowercase()))
+    .bind(payload.month_day)
+    .bind(payload.start_date.unwrap_or_else(|| Local::now().date_naive()))
+    .bind(payload.description)
+    .bind(payload.catch_up.unwrap_or(false))
+    .execute(&state.db)
+    .await?;
+
+    // Pay today's period right away instead of waiting for the next refresh
+    if let Err(e) = background::run_allowance_schedules(&state).await {
+        tracing::warn!(error = ?e, "scheduled allowance failed");
+    }
+
+    let schedule = query_as::<_, AllowanceSchedule>("SELECT * FROM allowance_schedules WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(schedule))
+}
+
+pub async fn update_schedule(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateScheduleSchema>,
+) -> Result<Json<AllowanceSchedule>, AppError> {
+    require_admin(&auth)?;
+
+    let schedule = query_as::<_, AllowanceSchedule>("SELECT * FROM allowance_schedules WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Schedule not found".to_string()))?;
+
+    validate_schedule(
+        payload.amount.unwrap_or(schedule.amount),
+        payload.frequency.unwrap_or(schedule.frequency),
+        payload.weekday.as_deref().or(schedule.weekday.as_deref()),
+        payload.month_day.or(schedule.month_day),
+        payload.description.as_deref(),
+    )?;
+
+    sqlx::query(
+        r#"
+        UPDATE allowance_schedules
+        SET
+            amount = COALESCE($1, amount),
+            frequency = COALESCE($2, frequency),
+            weekday = COALESCE($3, weekday),
+            month_day = COALESCE($4, month_day),
+            start_date = COALESCE($5, start_date),
+            description = COALESCE($6, description),
+            catch_up = COALESCE($7, catch_up),
+            active = COALESCE($8, active)
+        WHERE id = $9
+        "#
+    )
+    .bind(payload.amount)
+    .bind(payload.frequency)
+    .bind(payload.weekday.map(|w| w.trim().to_lowercase()))
+    .bind(payload.month_day)
+    .bind(payload.start_date)
+    .bind(payload.description)
+    .bind(payload.catch_up)
+    .bind(payload.active)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    let schedule = query_as::<_, AllowanceSchedule>("SELECT * FROM allowance_schedules WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(schedule))
+}
+
+pub async fn delete_schedule(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM allowance_schedules WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Schedule not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_payouts(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AllowancePayout>>, AppError> {
+    let schedule = query_as::<_, AllowanceSchedule>("SELECT * FROM allowance_schedules WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Schedule not found".to_string()))?;
+
+    if !auth.is_admin() && auth.user_id != schedule.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    let payouts = query_as::<_, AllowancePayout>(
+        "SELECT * FROM allowance_payouts WHERE schedule_id = $1 ORDER BY period_date DESC"
+    )
+        .bind(id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(payouts))
+}
+
+/// Check a bucket's name and that the user's split percentages don't pass 100
+async fn validate_bucket(
+    conn: &mut sqlx::SqliteConnection,
+    user_id: Uuid,
+    bucket_id: Option<Uuid>,
+    name: Option<&str>,
+    split_percent: Option<i64>,
+) -> Result<(), AppError> {
+    if name.is_some_and(|n| n.trim().is_empty() || n.len() > 50) {
+        return Err(AppError::InvalidInput("Name must be between 1 and 50 characters".to_string()));
+    }
+
+    if let Some(split_percent) = split_percent {
+        if !(0..=100).contains(&split_percent) {
+            return Err(AppError::InvalidInput("Split must be between 0 and 100 percent".to_string()));
+        }
+
+        let others: i64 = user_buckets(&mut *conn, user_id)
+            .await?
+            .iter()
+            .filter(|b| Some(b.id) != bucket_id)
+            .map(|b| b.split_percent)
+            .sum();
+
+        if others + split_percent > 100 {
+            return Err(AppError::InvalidInput("Bucket splits can't add up to more than 100 percent".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn list_buckets(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<BucketBalance>>, AppError> {
+    // Users can view their own buckets, admins can view anyone
+    if !auth.is_admin() && auth.user_id != user_id {
+        return Err(AppError::AuthError);
+    }
+
+    let mut conn = state.db.acquire().await?;
+    let buckets = bucket_balances(&mut conn, user_id).await?;
+
+    Ok(Json(buckets))
+}
+
+pub async fn create_bucket(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<CreateBucketSchema>,
+) -> Result<Json<AllowanceBucket>, AppError> {
+    require_admin(&auth)?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let split_percent = payload.split_percent.unwrap_or(0);
+    validate_bucket(&mut tx, user_id, None, Some(&payload.name), Some(split_percent)).await?;
+
+    let name_taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM allowance_buckets WHERE user_id = $1 AND name = $2)"
+    )
+        .bind(user_id)
+        .bind(payload.name.trim())
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if name_taken {
+        return Err(AppError::InvalidInput("Bucket name already in use".to_string()));
+    }
+
+    // New buckets go last unless placed explicitly
+    let position = match payload.position {
+        Some(position) => position,
+        None => sqlx::query_scalar(
+            "SELECT COALESCE(MAX(position) + 1, 0) FROM allowance_buckets WHERE user_id = $1"
+        )
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?,
+    };
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO allowance_buckets (id, user_id, name, split_percent, position) VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(id)
+    .bind(user_id)
+    .bind(payload.name.trim())
+    .bind(split_percent)
+    .bind(position)
+    .execute(&mut *tx)
+    .await?;
+
+    let bucket = query_as::<_, AllowanceBucket>("SELECT * FROM allowance_buckets WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(bucket))
+}
+
+pub async fn update_bucket(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateBucketSchema>,
+) -> Result<Json<AllowanceBucket>, AppError> {
+    require_admin(&auth)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let bucket = query_as::<_, AllowanceBucket>("SELECT * FROM allowance_buckets WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Bucket not found".to_string()))?;
+
+    validate_bucket(&mut tx, bucket.user_id, Some(id), payload.name.as_deref(), payload.split_percent).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE allowance_buckets
+        SET
+            name = COALESCE($1, name),
+            split_percent = COALESCE($2, split_percent),
+            position = COALESCE($3, position)
+        WHERE id = $4
+        "#
+    )
+    .bind(payload.name.as_deref().map(str::trim))
+    .bind(payload.split_percent)
+    .bind(payload.position)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let bucket = query_as::<_, AllowanceBucket>("SELECT * FROM allowance_buckets WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(bucket))
+}
+
+pub async fn delete_bucket(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let balance: Option<i64> = sqlx::query_scalar(
+        r#"
+        SELECT COALESCE((SELECT SUM(amount) FROM allowance_allocations WHERE bucket_id = b.id), 0)
+        FROM allowance_buckets b
+        WHERE b.id = $1
+        "#
+    )
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    match balance {
+        None => return Err(AppError::InvalidInput("Bucket not found".to_string())),
+        Some(balance) if balance != 0 => {
+            return Err(AppError::InvalidInput("Move the money out of the bucket before deleting it".to_string()));
+        }
+        Some(_) => {}
+    }
+
+    sqlx::query("DELETE FROM allowance_buckets WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn transfer_bucket(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<BucketTransferSchema>,
+) -> Result<Json<AllowanceTransaction>, AppError> {
+    // Users can move money between their own buckets, admins between anyone's
+    if !auth.is_admin() && auth.user_id != user_id {
+        return Err(AppError::AuthError);
+    }
+
+    if payload.amount <= 0 || payload.amount > MAX_REQUEST_AMOUNT {
+        return Err(AppError::InvalidInput("Amount must be positive and at most 1,000,000.00".to_string()));
+    }
+
+    if payload.from_bucket_id == Some(payload.to_bucket_id) {
+        return Err(AppError::InvalidInput("Choose two different buckets".to_string()));
+    }
+
+    if payload.description.as_ref().is_some_and(|d| d.len() > 500) {
+        return Err(AppError::InvalidInput("Description too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let buckets = bucket_balances(&mut tx, user_id).await?;
+    let bucket = |id: Uuid| {
+        buckets
+            .iter()
+            .find(|b| b.bucket_id == id)
+            .ok_or(AppError::InvalidInput("Bucket not found".to_string()))
+    };
+
+    let to = bucket(payload.to_bucket_id)?;
+    let (from_name, available) = match payload.from_bucket_id {
+        Some(from_id) => {
+            let from = bucket(from_id)?;
+            (from.name.clone(), from.balance)
+        }
+        None => {
+            let total: Option<i64> = sqlx::query_scalar(
+                "SELECT balance FROM allowance_ledger WHERE user_id = $1 ORDER BY seq DESC LIMIT 1"
+            )
+                .bind(user_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            let allocated: i64 = buckets.iter().map(|b| b.balance).sum();
+            ("Unallocated".to_string(), total.unwrap_or(0) - allocated)
+        }
+    };
+
+    if available < payload.amount {
+        return Err(AppError::InvalidInput(format!("Not enough money in {}", from_name)));
+    }
+
+    let description = payload
+        .description
+        .unwrap_or_else(|| format!("Moved from {} to {}", from_name, to.name));
+
+    let transaction = transfer_between_buckets(
+        &mut tx,
+        user_id,
+        payload.from_bucket_id,
+        payload.to_bucket_id,
+        payload.amount,
+        description,
+    ).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(transaction))
+}
+
+/// Months shown by the interest preview when none are asked for
+const DEFAULT_PREVIEW_MONTHS: u32 = 12;
+
+/// Largest hypothetical balance the preview will project, in cents
+const MAX_PREVIEW_BALANCE: i64 = 1_000_000_000_000;
+
+fn validate_rate(annual_rate: Decimal) -> Result<(), AppError> {
+    if annual_rate < Decimal::ZERO || annual_rate > Decimal::ONE_HUNDRED {
+        return Err(AppError::InvalidInput("Interest rate must be between 0 and 100 percent".to_string()));
+    }
+
+    if annual_rate.normalize().scale() > 4 {
+        return Err(AppError::InvalidInput("Interest rate can have at most 4 decimal places".to_string()));
+    }
+
+    Ok(())
+}
+
+pub async fn list_interest_rates(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<InterestRate>>, AppError> {
+    // Users see their own rates, admins see everyone's
+    let rates = query_as::<_, InterestRate>(
+        "SELECT * FROM interest_rates WHERE $1 OR user_id = $2 ORDER BY created_at ASC"
+    )
+        .bind(auth.is_admin())
+        .bind(auth.user_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(rates))
+}
+
+pub async fn create_interest_rate(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateInterestSchema>,
+) -> Result<Json<InterestRate>, AppError> {
+    require_admin(&auth)?;
+
+    validate_rate(payload.annual_rate)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(payload.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    if let Some(bucket_id) = payload.bucket_id
+        && !user_buckets(&mut tx, payload.user_id).await?.iter().any(|b| b.id == bucket_id)
+    {
+        return Err(AppError::InvalidInput("Bucket not found".to_string()));
+    }
+
+    // A whole-balance rate already covers every bucket, so it can't be mixed
+    // with bucket rates; otherwise the same money would earn interest twice
+    let overlaps: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM interest_rates
+            WHERE user_id = $1 AND ($2 IS NULL OR bucket_id IS NULL OR bucket_id = $2)
+        )
+        "#
+    )
+        .bind(payload.user_id)
+        .bind(payload.bucket_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if overlaps {
+        return Err(AppError::InvalidInput(match payload.bucket_id {
+            Some(_) => "This bucket already earns interest through another rate".to_string(),
+            None => "Remove this user's other interest rates before setting one on the whole balance".to_string(),
+        }));
+    }
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO interest_rates (id, user_id, bucket_id, annual_rate) VALUES ($1, $2, $3, $4)"
+    )
+    .bind(id)
+    .bind(payload.user_id)
+    .bind(payload.bucket_id)
+    .bind(payload.annual_rate.normalize().to_string())
+    .execute(&mut *tx)
+    .await?;
+
+    let rate = query_as::<_, InterestRate>("SELECT * FROM interest_rates WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(rate))
+}
+
+pub async fn update_interest_rate(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateInterestSchema>,
+) -> Result<Json<InterestRate>, AppError> {
+    require_admin(&auth)?;
+
+    if let Some(annual_rate) = payload.annual_rate {
+        validate_rate(annual_rate)?;
+    }
+
+    let result = sqlx::query(
+        r#"
+        UPDATE interest_rates
+        SET
+            annual_rate = COALESCE($1, annual_rate),
+            active = COALESCE($2, active)
+        WHERE id = $3
+        "#
+    )
+    .bind(payload.annual_rate.map(|r| r.normalize().to_string()))
+    .bind(payload.active)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Interest rate not found".to_string()));
+    }
+
+    let rate = query_as::<_, InterestRate>("SELECT * FROM interest_rates WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(rate))
+}
+
+pub async fn delete_interest_rate(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM interest_rates WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Interest rate not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn list_interest_payouts(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<InterestPayout>>, AppError> {
+    let owner: Option<Uuid> = sqlx::query_scalar("SELECT user_id FROM interest_rates WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let owner = owner.ok_or(AppError::InvalidInput("Interest rate not found".to_string()))?;
+
+    if !auth.is_admin() && auth.user_id != owner {
+        return Err(AppError::AuthError);
+    }
+
+    let payouts = query_as::<_, InterestPayout>(
+        "SELECT * FROM interest_payouts WHERE rate_id = $1 ORDER BY period_date DESC"
+    )
+        .bind(id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(payouts))
+}
+
+/// Project how a balance would grow over the coming months
+pub async fn preview_interest(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<InterestPreviewQuery>,
+    auth: AuthUser,
+) -> Result<Json<InterestPreview>, AppError> {
+    // Users can preview their own savings, admins can preview anyone's
+    if !auth.is_admin() && auth.user_id != query.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    let months = query.months.unwrap_or(DEFAULT_PREVIEW_MONTHS);
+    if !(1..=120).contains(&months) {
+        return Err(AppError::InvalidInput("Months must be between 1 and 120".to_string()));
+    }
+
+    let mut conn = state.db.acquire().await?;
+
+    let annual_rate = match query.annual_rate {
+        Some(annual_rate) => annual_rate,
+        None => query_as::<_, InterestRate>(
+            "SELECT * FROM interest_rates WHERE user_id = $1 AND bucket_id IS $2"
+        )
+            .bind(query.user_id)
+            .bind(query.bucket_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or(AppError::InvalidInput("No interest rate set for this balance".to_string()))?
+            .rate(),
+    };
+
+    validate_rate(annual_rate)?;
+
+    let starting_balance = match query.balance {
+        Some(balance) if balance.abs() > MAX_PREVIEW_BALANCE => {
+            return Err(AppError::InvalidInput("Balance is too large to preview".to_string()));
+        }
+        Some(balance) => balance,
+        None => allowance_balance(&mut conn, query.user_id, query.bucket_id).await?,
+    };
+
+    let projection = project_interest(starting_balance, annual_rate, Local::now().date_naive(), months);
+
+    Ok(Json(InterestPreview {
+        annual_rate,
+        starting_balance,
+        total_interest: projection.iter().map(|p| p.interest).sum(),
+        months: projection,
+    }))
+}
+
+pub const TRANSFER_SELECT: &str = r#"
+    SELECT t.*, fu.name as from_user_name, tu.name as to_user_name
+    FROM allowance_transfers t
+    LEFT JOIN users fu ON t.from_user_id = fu.id
+    LEFT JOIN users tu ON t.to_user_id = tu.id
+"#;
+
+pub async fn list_transfers(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AllowanceTransfer>>, AppError> {
+    // Users see transfers they're part of, admins see everyone's
+    let transfers = query_as::<_, AllowanceTransfer>(&format!(
+        r#"{}
+        WHERE $1 OR t.from_user_id = $2 OR t.to_user_id = $2
+        ORDER BY t.created_at DESC
+        "#,
+        TRANSFER_SELECT
+    ))
+    .bind(auth.is_admin())
+    .bind(auth.user_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(transfers))
+}
+
+/// Move money from one user's allowance to another's.
+///
+/// The debit, the credit and the transfer record are written in one
+/// transaction, each entry linked to the transfer. Children can only send
+/// their own money and no more than they have; parents can move money
+/// between anyone within the overdraft policy.
+pub async fn create_transfer(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateTransferSchema>,
+) -> Result<Json<AllowanceTransfer>, AppError> {
+    let from_user_id = payload.from_user_id.unwrap_or(auth.user_id);
+
+    if !auth.is_admin() && from_user_id != auth.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    if payload.amount <= 0 || payload.amount > MAX_REQUEST_AMOUNT {
+        return Err(AppError::InvalidInput("Amount must be positive and at most 1,000,000.00".to_string()));
+    }
+
+    if from_user_id == payload.to_user_id {
+        return Err(AppError::InvalidInput("Can't transfer to the same person".to_string()));
+    }
+
+    let description = payload.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    if description.as_ref().is_some_and(|d| d.len() > 200) {
+        return Err(AppError::InvalidInput("Description too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let from_name: Option<String> = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+        .bind(from_user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    let to_name: Option<String> = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+        .bind(payload.to_user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    let (Some(from_name), Some(to_name)) = (from_name, to_name) else {
+        return Err(AppError::UserNotFound);
+    };
+
+    if auth.is_admin() {
+        check_overdraft(&mut tx, from_user_id, None, payload.amount).await?;
+    } else {
+        // Money promised to pending spending requests isn't free to send
+        let pending: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(amount), 0) FROM spending_requests WHERE user_id = $1 AND status = 'pending'"
+        )
+            .bind(from_user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if allowance_balance(&mut tx, from_user_id, None).await? - pending < payload.amount {
+            return Err(AppError::InvalidInput("Not enough money".to_string()));
+        }
+    }
+
+    let id = Uuid::new_v4();
+    let suffix = description.as_ref().map(|d| format!(": {}", d)).unwrap_or_default();
+
+    let debit = post_entry(&mut tx, NewLedgerEntry {
+        user_id: from_user_id,
+        amount: -payload.amount,
+        description: format!("Transfer to {}{}", to_name, suffix),
+        transfer_id: Some(id),
+        ..Default::default()
+    }).await?;
+
+    let credit = post_entry(&mut tx, NewLedgerEntry {
+        user_id: payload.to_user_id,
+        amount: payload.amount,
+        description: format!("Transfer from {}{}", from_name, suffix),
+        transfer_id: Some(id),
+        ..Default::default()
+    }).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO allowance_transfers (id, from_user_id, to_user_id, amount, description,
+                                         debit_entry_id, credit_entry_id, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#
+    )
+    .bind(id)
+    .bind(from_user_id)
+    .bind(payload.to_user_id)
+    .bind(payload.amount)
+    .bind(description)
+    .bind(debit.id)
+    .bind(credit.id)
+    .bind(auth.user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let transfer = query_as::<_, AllowanceTransfer>(&format!("{} WHERE t.id = $1", TRANSFER_SELECT))
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(transfer_id = %id, from = %from_user_id, to = %payload.to_user_id, amount = payload.amount, "Allowance transferred");
+
+    Ok(Json(transfer))
+}
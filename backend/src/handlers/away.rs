@@ -0,0 +1,114 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use sqlx::{query_as, SqliteConnection};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::away::{AwayPeriod, CreateAwayPeriodSchema},
+    state::AppState,
+    utils::auth_helpers::require_admin,
+    middleware::auth::AuthUser,
+};
+
+/// Load every family member's away periods
+pub async fn load_away_periods(conn: &mut SqliteConnection) -> Result<Vec<AwayPeriod>, AppError> {
+    let periods = query_as::<_, AwayPeriod>(
+        "SELECT * FROM away_periods ORDER BY start_date ASC"
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(periods)
+}
+
+pub async fn list_away_periods(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<AwayPeriod>>, AppError> {
+    // Users can view their own away periods, admins can view anyone
+    if !auth.is_admin() && auth.user_id != user_id {
+        return Err(AppError::AuthError);
+    }
+
+    let periods = query_as::<_, AwayPeriod>(
+        "SELECT * FROM away_periods WHERE user_id = $1 ORDER BY start_date DESC"
+    )
+    .bind(user_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(periods))
+}
+
+pub async fn create_away_period(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<CreateAwayPeriodSchema>,
+) -> Result<Json<AwayPeriod>, AppError> {
+    require_admin(&auth)?;
+
+    if payload.end_date < payload.start_date {
+        return Err(AppError::InvalidInput("Away period must end on or after its start".to_string()));
+    }
+
+    if payload.note.as_ref().is_some_and(|n| n.len() > 200) {
+        return Err(AppError::InvalidInput("Note too long".to_string()));
+    }
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO away_periods (id, user_id, start_date, end_date, note) VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(id)
+    .bind(user_id)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(payload.note)
+    .execute(&state.db)
+    .await?;
+
+    let period = query_as::<_, AwayPeriod>("SELECT * FROM away_periods WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(period))
+}
+
+pub async fn delete_away_period(
+    State(state): State<Arc<AppState>>,
+    Path((user_id, id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM away_periods WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Away period not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -4,17 +4,28 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use sqlx::query_as;
+use uuid::Uuid;
 
 use crate::{
     error::AppError,
     models::{
         backup::BackupData,
         user::{BackupUser, AllowanceTransaction, UserRole},
+        allowance::{
+            AllowanceAllocation, AllowanceBucket, AllowancePayout, AllowanceSchedule, AllowanceTransfer,
+            InterestPayout, InterestRate,
+        },
+        reward::{PointsTransaction, Redemption, Reward},
+        away::AwayPeriod,
+        goal::SavingsGoal,
+        spending::SpendingRequest,
+        routine::{Routine, RoutineCompletion, RoutineStep, RoutineStepCheck},
         settings::Setting,
         calendar::Calendar,
     },
+    handlers::{allowance::TRANSFER_SELECT, spending::SPENDING_SELECT},
     state::AppState,
     utils::auth_helpers::require_admin,
     middleware::auth::AuthUser,
@@ -32,7 +43,43 @@ pub async fn export_backup(
         .fetch_all(&state.db).await?;
     let calendars = query_as::<_, Calendar>("SELECT * FROM calendars")
         .fetch_all(&state.db).await?;
-    let ledger = query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger")
+    let ledger = query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger ORDER BY seq")
+        .fetch_all(&state.db).await?;
+    let transfers = query_as::<_, AllowanceTransfer>(&format!("{} ORDER BY t.created_at", TRANSFER_SELECT))
+        .fetch_all(&state.db).await?;
+    let rewards = query_as::<_, Reward>("SELECT * FROM rewards")
+        .fetch_all(&state.db).await?;
+    let points_ledger = query_as::<_, PointsTransaction>("SELECT * FROM points_ledger ORDER BY seq")
+        .fetch_all(&state.db).await?;
+    let reward_redemptions = query_as::<_, Redemption>(
+        "SELECT r.*, u.name as user_name FROM reward_redemptions r JOIN users u ON r.user_id = u.id ORDER BY r.created_at"
+    )
+        .fetch_all(&state.db).await?;
+    let routines = query_as::<_, Routine>("SELECT * FROM routines")
+        .fetch_all(&state.db).await?;
+    let routine_steps = query_as::<_, RoutineStep>("SELECT * FROM routine_steps")
+        .fetch_all(&state.db).await?;
+    let routine_step_checks = query_as::<_, RoutineStepCheck>("SELECT * FROM routine_step_checks")
+        .fetch_all(&state.db).await?;
+    let routine_completions = query_as::<_, RoutineCompletion>("SELECT * FROM routine_completions")
+        .fetch_all(&state.db).await?;
+    let away_periods = query_as::<_, AwayPeriod>("SELECT * FROM away_periods")
+        .fetch_all(&state.db).await?;
+    let allowance_schedules = query_as::<_, AllowanceSchedule>("SELECT * FROM allowance_schedules")
+        .fetch_all(&state.db).await?;
+    let allowance_payouts = query_as::<_, AllowancePayout>("SELECT * FROM allowance_payouts")
+        .fetch_all(&state.db).await?;
+    let allowance_buckets = query_as::<_, AllowanceBucket>("SELECT * FROM allowance_buckets")
+        .fetch_all(&state.db).await?;
+    let allowance_allocations = query_as::<_, AllowanceAllocation>("SELECT * FROM allowance_allocations")
+        .fetch_all(&state.db).await?;
+    let savings_goals = query_as::<_, SavingsGoal>("SELECT * FROM savings_goals")
+        .fetch_all(&state.db).await?;
+    let interest_rates = query_as::<_, InterestRate>("SELECT * FROM interest_rates")
+        .fetch_all(&state.db).await?;
+    let interest_payouts = query_as::<_, InterestPayout>("SELECT * FROM interest_payouts")
+        .fetch_all(&state.db).await?;
+    let spending_requests = query_as::<_, SpendingRequest>(&format!("{} ORDER BY s.created_at", SPENDING_SELECT))
         .fetch_all(&state.db).await?;
 
     let backup = BackupData {
@@ -40,6 +87,23 @@ pub async fn export_backup(
         settings,
         calendars,
         allowance_ledger: ledger,
+        allowance_transfers: transfers,
+        rewards,
+        points_ledger,
+        reward_redemptions,
+        routines,
+        routine_steps,
+        routine_step_checks,
+        routine_completions,
+        away_periods,
+        allowance_schedules,
+        allowance_payouts,
+        allowance_buckets,
+        allowance_allocations,
+        savings_goals,
+        interest_rates,
+        interest_payouts,
+        spending_requests,
         version: 1,
         created_at: chrono::Utc::now(),
     };
@@ -64,6 +128,41 @@ pub async fn import_backup(
 
     let mut tx = state.db.begin().await.map_err(AppError::Sqlx)?;
 
+    // Chores aren't part of the backup and keep their ids, so note which entries they
+    // point at before those are deleted and link them to the restored ones at the end
+    let chore_entries = query_as::<_, (Uuid, Option<Uuid>, Option<Uuid>, Option<Uuid>)>(
+        "SELECT id, reward_entry_id, penalty_entry_id, points_entry_id FROM chores
+         WHERE reward_entry_id IS NOT NULL OR penalty_entry_id IS NOT NULL OR points_entry_id IS NOT NULL"
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(AppError::Sqlx)?;
+
+    // The admin's own rows in these survive deleting the other users
+    sqlx::query("DELETE FROM reward_redemptions")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM points_ledger")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM rewards")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    // Steps, checks and completions go with their routines
+    sqlx::query("DELETE FROM routines")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM away_periods")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM allowance_schedules")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    // Allocations go with their buckets
+    sqlx::query("DELETE FROM allowance_buckets")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM savings_goals")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM interest_rates")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM spending_requests")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
+    sqlx::query("DELETE FROM allowance_transfers")
+        .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
     sqlx::query("DELETE FROM allowance_ledger")
         .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
     sqlx::query("DELETE FROM calendars")
@@ -75,7 +174,7 @@ pub async fn import_backup(
         .bind(auth.user_id)
         .execute(&mut *tx).await.map_err(AppError::Sqlx)?;
 
-    let mut user_id_map = std::collections::HashMap::new();
+    let mut user_id_map = HashMap::new();
 
     // Map the old admin/owner to the current one
     if let Some(old_admin) = backup.users.iter().find(|u| matches!(u.role, UserRole::Admin)) {
@@ -87,16 +186,17 @@ pub async fn import_backup(
             continue;
         }
         
-        let new_id = uuid::Uuid::new_v4();
+        let new_id = Uuid::new_v4();
         user_id_map.insert(user.id, new_id);
 
         sqlx::query(
-            "INSERT INTO users (id, username, name, password_hash, birthday, profile_picture_url, role, track_allowance, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+            "INSERT INTO users (id, username, name, password_hash, pin_hash, birthday, profile_picture_url, role, track_allowance, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
         )
         .bind(new_id)
         .bind(user.username)
         .bind(user.name)
         .bind(user.password_hash)
+        .bind(user.pin_hash)
         .bind(user.birthday)
         .bind(user.profile_picture_url)
         .bind(user.role.to_string())
@@ -125,7 +225,7 @@ pub async fn import_backup(
         sqlx::query(
             "INSERT INTO calendars (id, name, url, google_id, color, created_at) VALUES ($1, $2, $3, $4, $5, $6)"
         )
-        .bind(uuid::Uuid::new_v4())
+        .bind(Uuid::new_v4())
         .bind(calendar.name)
         .bind(calendar.url)
         .bind(calendar.google_id)
@@ -136,16 +236,34 @@ pub async fn import_backup(
         .map_err(AppError::Sqlx)?;
     }
 
+    // Entries get new ids, so links between them are rewritten to match.
+    // Transfers are written after the entries; the ledger's transfer_id check is deferred.
+    let transfer_id_map: HashMap<Uuid, Uuid> = backup.allowance_transfers
+        .iter()
+        .map(|t| (t.id, Uuid::new_v4()))
+        .collect();
+    let mut entry_id_map = HashMap::new();
+
+    // Oldest first, so an entry is always restored before the reversal pointing at it
     for entry in backup.allowance_ledger {
         if let Some(new_user_id) = user_id_map.get(&entry.user_id) {
+            let new_id = Uuid::new_v4();
+            entry_id_map.insert(entry.id, new_id);
+
             sqlx::query(
-                "INSERT INTO allowance_ledger (id, user_id, amount, balance, description, created_at) VALUES ($1, $2, $3, $4, $5, $6)"
+                "INSERT INTO allowance_ledger (id, user_id, amount, balance, description, chore_id, reversal_of, voided_at, voided_by, transfer_id, created_at)
+                 VALUES ($1, $2, $3, $4, $5, (SELECT id FROM chores WHERE id = $6), $7, $8, $9, $10, $11)"
             )
-            .bind(uuid::Uuid::new_v4())
+            .bind(new_id)
             .bind(new_user_id)
             .bind(entry.amount)
             .bind(entry.balance)
             .bind(entry.description)
+            .bind(entry.chore_id)
+            .bind(entry.reversal_of.and_then(|id| entry_id_map.get(&id)))
+            .bind(entry.voided_at)
+            .bind(entry.voided_by.and_then(|id| user_id_map.get(&id)))
+            .bind(entry.transfer_id.and_then(|id| transfer_id_map.get(&id)))
             .bind(entry.created_at)
             .execute(&mut *tx)
             .await
@@ -153,6 +271,329 @@ pub async fn import_backup(
         }
     }
 
+    for transfer in backup.allowance_transfers {
+        sqlx::query(
+            "INSERT INTO allowance_transfers (id, from_user_id, to_user_id, amount, description, debit_entry_id, credit_entry_id, created_by, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+        )
+        .bind(transfer_id_map[&transfer.id])
+        .bind(transfer.from_user_id.and_then(|id| user_id_map.get(&id)))
+        .bind(transfer.to_user_id.and_then(|id| user_id_map.get(&id)))
+        .bind(transfer.amount)
+        .bind(transfer.description)
+        .bind(transfer.debit_entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(transfer.credit_entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(transfer.created_by.and_then(|id| user_id_map.get(&id)))
+        .bind(transfer.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    for reward in backup.rewards {
+        sqlx::query(
+            "INSERT INTO rewards (id, name, description, cost, active, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7)"
+        )
+        .bind(reward.id)
+        .bind(reward.name)
+        .bind(reward.description)
+        .bind(reward.cost)
+        .bind(reward.active)
+        .bind(reward.created_at)
+        .bind(reward.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    // Chores aren't in the backup, so an entry keeps its chore only if that chore is still here
+    for entry in backup.points_ledger {
+        if let Some(new_user_id) = user_id_map.get(&entry.user_id) {
+            sqlx::query(
+                "INSERT INTO points_ledger (id, user_id, amount, balance, description, chore_id, created_at) VALUES ($1, $2, $3, $4, $5, (SELECT id FROM chores WHERE id = $6), $7)"
+            )
+            .bind(entry.id)
+            .bind(new_user_id)
+            .bind(entry.amount)
+            .bind(entry.balance)
+            .bind(entry.description)
+            .bind(entry.chore_id)
+            .bind(entry.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for redemption in backup.reward_redemptions {
+        if let Some(new_user_id) = user_id_map.get(&redemption.user_id) {
+            sqlx::query(
+                "INSERT INTO reward_redemptions (id, user_id, reward_id, reward_name, cost, status, note, reviewed_by, reviewed_at, points_entry_id, created_at)
+                 VALUES ($1, $2, (SELECT id FROM rewards WHERE id = $3), $4, $5, $6, $7, $8, $9, (SELECT id FROM points_ledger WHERE id = $10), $11)"
+            )
+            .bind(redemption.id)
+            .bind(new_user_id)
+            .bind(redemption.reward_id)
+            .bind(redemption.reward_name)
+            .bind(redemption.cost)
+            .bind(redemption.status)
+            .bind(redemption.note)
+            .bind(redemption.reviewed_by.and_then(|id| user_id_map.get(&id)))
+            .bind(redemption.reviewed_at)
+            .bind(redemption.points_entry_id)
+            .bind(redemption.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for routine in backup.routines {
+        if let Some(new_user_id) = user_id_map.get(&routine.user_id) {
+            sqlx::query(
+                "INSERT INTO routines (id, user_id, name, window_start, window_end, reward, active, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+            )
+            .bind(routine.id)
+            .bind(new_user_id)
+            .bind(routine.name)
+            .bind(routine.window_start)
+            .bind(routine.window_end)
+            .bind(routine.reward)
+            .bind(routine.active)
+            .bind(routine.created_at)
+            .bind(routine.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    // Rows of a routine that wasn't restored are dropped with it
+    for step in backup.routine_steps {
+        sqlx::query(
+            "INSERT INTO routine_steps (id, routine_id, position, description)
+             SELECT $1, $2, $3, $4 WHERE EXISTS (SELECT 1 FROM routines WHERE id = $2)"
+        )
+        .bind(step.id)
+        .bind(step.routine_id)
+        .bind(step.position)
+        .bind(step.description)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    for check in backup.routine_step_checks {
+        sqlx::query(
+            "INSERT INTO routine_step_checks (step_id, check_date, checked_by, checked_at)
+             SELECT $1, $2, $3, $4 WHERE EXISTS (SELECT 1 FROM routine_steps WHERE id = $1)"
+        )
+        .bind(check.step_id)
+        .bind(check.check_date)
+        .bind(check.checked_by.and_then(|id| user_id_map.get(&id)))
+        .bind(check.checked_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    for completion in backup.routine_completions {
+        sqlx::query(
+            "INSERT INTO routine_completions (routine_id, completion_date, reward_entry_id, completed_at)
+             SELECT $1, $2, $3, $4 WHERE EXISTS (SELECT 1 FROM routines WHERE id = $1)"
+        )
+        .bind(completion.routine_id)
+        .bind(completion.completion_date)
+        .bind(completion.reward_entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(completion.completed_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    for period in backup.away_periods {
+        if let Some(new_user_id) = user_id_map.get(&period.user_id) {
+            sqlx::query(
+                "INSERT INTO away_periods (id, user_id, start_date, end_date, note, created_at) VALUES ($1, $2, $3, $4, $5, $6)"
+            )
+            .bind(period.id)
+            .bind(new_user_id)
+            .bind(period.start_date)
+            .bind(period.end_date)
+            .bind(period.note)
+            .bind(period.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for schedule in backup.allowance_schedules {
+        if let Some(new_user_id) = user_id_map.get(&schedule.user_id) {
+            sqlx::query(
+                "INSERT INTO allowance_schedules (id, user_id, amount, frequency, weekday, month_day, start_date, description, catch_up, active, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"
+            )
+            .bind(schedule.id)
+            .bind(new_user_id)
+            .bind(schedule.amount)
+            .bind(schedule.frequency)
+            .bind(schedule.weekday)
+            .bind(schedule.month_day)
+            .bind(schedule.start_date)
+            .bind(schedule.description)
+            .bind(schedule.catch_up)
+            .bind(schedule.active)
+            .bind(schedule.created_at)
+            .bind(schedule.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    // Without its payouts a restored schedule would pay every past period again
+    for payout in backup.allowance_payouts {
+        sqlx::query(
+            "INSERT INTO allowance_payouts (schedule_id, period_date, entry_id, skipped, created_at)
+             SELECT $1, $2, $3, $4, $5 WHERE EXISTS (SELECT 1 FROM allowance_schedules WHERE id = $1)"
+        )
+        .bind(payout.schedule_id)
+        .bind(payout.period_date)
+        .bind(payout.entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(payout.skipped)
+        .bind(payout.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    for bucket in backup.allowance_buckets {
+        if let Some(new_user_id) = user_id_map.get(&bucket.user_id) {
+            sqlx::query(
+                "INSERT INTO allowance_buckets (id, user_id, name, split_percent, position, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7)"
+            )
+            .bind(bucket.id)
+            .bind(new_user_id)
+            .bind(bucket.name)
+            .bind(bucket.split_percent)
+            .bind(bucket.position)
+            .bind(bucket.created_at)
+            .bind(bucket.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for allocation in backup.allowance_allocations {
+        if let Some(new_entry_id) = entry_id_map.get(&allocation.entry_id) {
+            sqlx::query(
+                "INSERT INTO allowance_allocations (entry_id, bucket_id, amount)
+                 SELECT $1, $2, $3 WHERE EXISTS (SELECT 1 FROM allowance_buckets WHERE id = $2)"
+            )
+            .bind(new_entry_id)
+            .bind(allocation.bucket_id)
+            .bind(allocation.amount)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for goal in backup.savings_goals {
+        if let Some(new_user_id) = user_id_map.get(&goal.user_id) {
+            sqlx::query(
+                "INSERT INTO savings_goals (id, user_id, bucket_id, name, target_amount, image_url, target_date, active, created_at, updated_at)
+                 VALUES ($1, $2, (SELECT id FROM allowance_buckets WHERE id = $3), $4, $5, $6, $7, $8, $9, $10)"
+            )
+            .bind(goal.id)
+            .bind(new_user_id)
+            .bind(goal.bucket_id)
+            .bind(goal.name)
+            .bind(goal.target_amount)
+            .bind(goal.image_url)
+            .bind(goal.target_date)
+            .bind(goal.active)
+            .bind(goal.created_at)
+            .bind(goal.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    // A bucket's rate is dropped with its bucket rather than turned into a whole-balance rate
+    for rate in backup.interest_rates {
+        if let Some(new_user_id) = user_id_map.get(&rate.user_id) {
+            sqlx::query(
+                "INSERT INTO interest_rates (id, user_id, bucket_id, annual_rate, active, created_at, updated_at)
+                 SELECT $1, $2, $3, $4, $5, $6, $7 WHERE $3 IS NULL OR EXISTS (SELECT 1 FROM allowance_buckets WHERE id = $3)"
+            )
+            .bind(rate.id)
+            .bind(new_user_id)
+            .bind(rate.bucket_id)
+            .bind(rate.annual_rate)
+            .bind(rate.active)
+            .bind(rate.created_at)
+            .bind(rate.updated_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for payout in backup.interest_payouts {
+        sqlx::query(
+            "INSERT INTO interest_payouts (rate_id, period_date, entry_id, balance, amount, created_at)
+             SELECT $1, $2, $3, $4, $5, $6 WHERE EXISTS (SELECT 1 FROM interest_rates WHERE id = $1)"
+        )
+        .bind(payout.rate_id)
+        .bind(payout.period_date)
+        .bind(payout.entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(payout.balance)
+        .bind(payout.amount)
+        .bind(payout.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
+    for request in backup.spending_requests {
+        if let Some(new_user_id) = user_id_map.get(&request.user_id) {
+            sqlx::query(
+                "INSERT INTO spending_requests (id, user_id, bucket_id, amount, description, status, note, reviewed_by, reviewed_at, entry_id, created_at)
+                 VALUES ($1, $2, (SELECT id FROM allowance_buckets WHERE id = $3), $4, $5, $6, $7, $8, $9, $10, $11)"
+            )
+            .bind(request.id)
+            .bind(new_user_id)
+            .bind(request.bucket_id)
+            .bind(request.amount)
+            .bind(request.description)
+            .bind(request.status)
+            .bind(request.note)
+            .bind(request.reviewed_by.and_then(|id| user_id_map.get(&id)))
+            .bind(request.reviewed_at)
+            .bind(request.entry_id.and_then(|id| entry_id_map.get(&id)))
+            .bind(request.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::Sqlx)?;
+        }
+    }
+
+    for (chore_id, reward_entry_id, penalty_entry_id, points_entry_id) in chore_entries {
+        sqlx::query(
+            "UPDATE chores SET reward_entry_id = $1, penalty_entry_id = $2, points_entry_id = (SELECT id FROM points_ledger WHERE id = $3) WHERE id = $4"
+        )
+        .bind(reward_entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(penalty_entry_id.and_then(|id| entry_id_map.get(&id)))
+        .bind(points_entry_id)
+        .bind(chore_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::Sqlx)?;
+    }
+
     tx.commit().await.map_err(AppError::Sqlx)?;
 
     Ok(StatusCode::OK)
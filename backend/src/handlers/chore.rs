@@ -1,32 +1,52 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Multipart, Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
     Json,
 };
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
-use sqlx::query_as;
+use chrono::{Duration, Local, NaiveDate, NaiveTime};
+use sqlx::{query_as, SqliteConnection};
 use uuid::Uuid;
 
 use crate::{
+    background,
     error::AppError,
-    models::chore::{Chore, ChoreWithUser, CreateChoreSchema, UpdateChoreSchema},
+    handlers::away::load_away_periods,
+    models::{
+        chore::{
+            due_deadline, parse_weekdays, Chore, ChoreEvent, ChoreEventType, ChoreFilter,
+            ChoreRotation, ChoreStats, ChoreStatsQuery, ChoreWithUser, CreateChoreSchema,
+            DisplayCompleteChoreSchema, ListChoresQuery, Recurrence, ReviewChoreSchema, ReviewStatus, RotationMember,
+            RotationPreview, RotationPreviewQuery, UpdateChoreSchema, UpdateRotationSchema,
+        },
+        reward::PointsTransaction,
+        user::{AllowanceTransaction, UserRole},
+    },
     state::AppState,
-    utils::auth_helpers::require_admin,
-    middleware::auth::AuthUser,
+    utils::{
+        auth_helpers::{require_admin, verify_display_pin},
+        ledger::{post_entry, post_points_entry, NewLedgerEntry, NewPointsEntry, MAX_REQUEST_AMOUNT},
+        photo_files::{image_extension, photo_content_type, photo_path, CHORE_PROOFS_DIR},
+    },
+    middleware::auth::{AuthUser, DisplayAuth},
 };
 
 pub async fn list_chores(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<ListChoresQuery>,
     auth: AuthUser,
 ) -> Result<Json<Vec<ChoreWithUser>>, AppError> {
     let chores = if auth.is_admin() {
         query_as::<_, ChoreWithUser>(
             r#"
-            SELECT c.id, c.description, c.assigned_to, u.name as assigned_name, 
-                   c.reward, c.completed, c.created_at, c.updated_at
+            SELECT c.*, u.name as assigned_name, r.name as reviewer_name
             FROM chores c
-            JOIN users u ON c.assigned_to = u.id
-            ORDER BY c.completed ASC, c.created_at DESC
+            LEFT JOIN users u ON c.assigned_to = u.id
+            LEFT JOIN users r ON c.reviewed_by = r.id
+            WHERE c.recurrence IS NULL
+            ORDER BY c.completed ASC, c.occurrence_date DESC, c.created_at DESC
             "#
         )
         .fetch_all(&state.db)
@@ -34,12 +54,12 @@ pub async fn list_chores(
     } else {
         query_as::<_, ChoreWithUser>(
             r#"
-            SELECT c.id, c.description, c.assigned_to, u.name as assigned_name, 
-                   c.reward, c.completed, c.created_at, c.updated_at
+            SELECT c.*, u.name as assigned_name, r.name as reviewer_name
             FROM chores c
             JOIN users u ON c.assigned_to = u.id
-            WHERE c.assigned_to = $1
-            ORDER BY c.completed ASC, c.created_at DESC
+            LEFT JOIN users r ON c.reviewed_by = r.id
+            WHERE c.assigned_to = $1 AND c.recurrence IS NULL
+            ORDER BY c.completed ASC, c.occurrence_date DESC, c.created_at DESC
             "#
         )
         .bind(auth.user_id)
@@ -47,9 +67,234 @@ pub async fn list_chores(
         .await?
     };
 
+    let mut conn = state.db.acquire().await?;
+    let away = load_away_periods(&mut conn).await?;
+    drop(conn);
+
+    let now = Local::now().naive_local();
+    let today = now.date();
+
+    let chores = chores
+        .into_iter()
+        .map(|c| c.with_overdue(now, &away))
+        .filter(|c| match query.filter {
+            None => true,
+            Some(ChoreFilter::Today) => c.due_date == Some(today),
+            Some(ChoreFilter::Overdue) => c.overdue,
+            Some(ChoreFilter::Upcoming) => c.due_date.is_some_and(|d| d > today),
+        })
+        .collect();
+
+    Ok(Json(chores))
+}
+
+pub async fn list_open_chores(
+    State(state): State<Arc<AppState>>,
+    _auth: AuthUser,
+) -> Result<Json<Vec<ChoreWithUser>>, AppError> {
+    let chores = query_as::<_, ChoreWithUser>(
+        r#"
+        SELECT c.*, u.name as assigned_name, r.name as reviewer_name
+        FROM chores c
+        LEFT JOIN users u ON c.assigned_to = u.id
+        LEFT JOIN users r ON c.reviewed_by = r.id
+        WHERE c.claimable = 1 AND c.assigned_to IS NULL AND c.completed = 0 AND c.recurrence IS NULL
+        ORDER BY c.reward DESC, c.created_at ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let now = Local::now().naive_local();
+    let chores = chores.into_iter().map(|c| c.with_overdue(now, &[])).collect();
+
+    Ok(Json(chores))
+}
+
+pub async fn claim_chore(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Chore>, AppError> {
+    // Claim atomically so two people can't grab the same chore
+    let result = sqlx::query(
+        r#"
+        UPDATE chores
+        SET assigned_to = $1,
+            claimed_at = datetime('now'),
+            claim_expires_at = CASE WHEN claim_hours IS NULL THEN NULL
+                                    ELSE datetime('now', '+' || claim_hours || ' hours') END,
+            updated_at = datetime('now')
+        WHERE id = $2 AND claimable = 1 AND assigned_to IS NULL AND completed = 0 AND recurrence IS NULL
+        "#,
+    )
+    .bind(auth.user_id)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Chore is not available to claim".to_string()));
+    }
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(chore))
+}
+
+pub async fn release_chore(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Chore>, AppError> {
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    if !auth.is_admin() && chore.assigned_to != Some(auth.user_id) {
+        return Err(AppError::AuthError);
+    }
+
+    if !chore.claimable || chore.assigned_to.is_none() {
+        return Err(AppError::InvalidInput("Chore has not been claimed".to_string()));
+    }
+
+    if chore.completed || chore.review_status == Some(ReviewStatus::Pending) {
+        return Err(AppError::InvalidInput("Finished chores can't be released".to_string()));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE chores
+        SET assigned_to = NULL, claimed_at = NULL, claim_expires_at = NULL, review_status = NULL,
+            reviewed_by = NULL, reviewed_at = NULL, review_note = NULL, updated_at = datetime('now')
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(chore))
+}
+
+pub async fn list_recurring_chores(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChoreWithUser>>, AppError> {
+    require_admin(&auth)?;
+
+    let chores = query_as::<_, ChoreWithUser>(
+        r#"
+        SELECT c.*, u.name as assigned_name, r.name as reviewer_name
+        FROM chores c
+        LEFT JOIN users u ON c.assigned_to = u.id
+        LEFT JOIN users r ON c.reviewed_by = r.id
+        WHERE c.recurrence IS NOT NULL
+        ORDER BY c.created_at DESC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
     Ok(Json(chores))
 }
 
+/// Validate the time-of-day window a chore is due in
+pub fn validate_window(
+    start: Option<NaiveTime>,
+    end: Option<NaiveTime>,
+    label: Option<&str>,
+) -> Result<(), AppError> {
+    if let (Some(start), Some(end)) = (start, end)
+        && start >= end
+    {
+        return Err(AppError::InvalidInput("Window must start before it ends".to_string()));
+    }
+
+    if label.is_some_and(|l| l.len() > 100) {
+        return Err(AppError::InvalidInput("Window label too long".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Validate the allowance paid for completing a chore
+pub fn validate_reward(reward: Option<i64>) -> Result<(), AppError> {
+    if reward.is_some_and(|r| !(0..=MAX_REQUEST_AMOUNT).contains(&r)) {
+        return Err(AppError::InvalidInput("Reward must be between 0 and 1,000,000.00".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Validate the points earned for completing a chore
+pub fn validate_points(points: Option<i64>) -> Result<(), AppError> {
+    if points.is_some_and(|p| !(0..=MAX_REQUEST_AMOUNT).contains(&p)) {
+        return Err(AppError::InvalidInput("Points must be between 0 and 100,000,000".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Validate a missed-chore penalty and its grace period
+pub fn validate_penalty(penalty: Option<i64>, grace_minutes: Option<i64>) -> Result<(), AppError> {
+    if penalty.is_some_and(|p| p <= 0 || p > MAX_REQUEST_AMOUNT) {
+        return Err(AppError::InvalidInput("Penalty must be positive and at most 1,000,000.00".to_string()));
+    }
+
+    if grace_minutes.is_some_and(|g| !(0..=10080).contains(&g)) {
+        return Err(AppError::InvalidInput("Grace period must be between 0 and 10080 minutes".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Validate the recurrence rule of a chore series
+pub fn validate_recurrence(
+    recurrence: Option<Recurrence>,
+    weekdays: Option<&str>,
+    interval: Option<i64>,
+    day: Option<i64>,
+) -> Result<(), AppError> {
+    let Some(recurrence) = recurrence else {
+        return Ok(());
+    };
+
+    match recurrence {
+        Recurrence::Daily => {}
+        Recurrence::Weekly => {
+            let weekdays = weekdays.unwrap_or_default();
+            let days = parse_weekdays(weekdays).map_err(AppError::InvalidInput)?;
+            if days.is_empty() {
+                return Err(AppError::InvalidInput("Weekly chores need at least one weekday".to_string()));
+            }
+        }
+        Recurrence::EveryNDays => {
+            if !matches!(interval, Some(1..=365)) {
+                return Err(AppError::InvalidInput("Interval must be between 1 and 365 days".to_string()));
+            }
+        }
+        Recurrence::Monthly => {
+            if !matches!(day, Some(1..=31)) {
+                return Err(AppError::InvalidInput("Day of month must be between 1 and 31".to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn create_chore(
     State(state): State<Arc<AppState>>,
     auth: AuthUser,
@@ -61,29 +306,75 @@ pub async fn create_chore(
         return Err(AppError::InvalidInput("Description too long".to_string()));
     }
 
-    // Verify assigned user exists
-    let user_exists: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
-    )
-        .bind(payload.assigned_to)
-        .fetch_one(&state.db)
-        .await?;
+    validate_recurrence(
+        payload.recurrence,
+        payload.recurrence_weekdays.as_deref(),
+        payload.recurrence_interval,
+        payload.recurrence_day,
+    )?;
+
+    validate_window(payload.window_start, payload.window_end, payload.window_label.as_deref())?;
+
+    if payload.recurrence.is_some() && payload.due_date.is_some() {
+        return Err(AppError::InvalidInput("Recurring chores are due on each occurrence date".to_string()));
+    }
+
+    let recurrence_start = payload
+        .recurrence
+        .map(|_| payload.recurrence_start.unwrap_or_else(|| Local::now().date_naive()));
+
+    if payload.claim_hours.is_some_and(|h| !(1..=720).contains(&h)) {
+        return Err(AppError::InvalidInput("Claim duration must be between 1 and 720 hours".to_string()));
+    }
+
+    validate_reward(payload.reward)?;
+
+    validate_points(payload.points)?;
+
+    validate_penalty(payload.penalty, payload.penalty_grace_minutes)?;
+
+    // Verify assigned user exists; chores without one go on the open board
+    if let Some(assigned_to) = payload.assigned_to {
+        let user_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+        )
+            .bind(assigned_to)
+            .fetch_one(&state.db)
+            .await?;
 
-    if !user_exists {
-        return Err(AppError::InvalidInput("Assigned user not found".to_string()));
+        if !user_exists {
+            return Err(AppError::InvalidInput("Assigned user not found".to_string()));
+        }
     }
 
     let id = Uuid::new_v4();
     sqlx::query(
         r#"
-        INSERT INTO chores (id, description, assigned_to, reward)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO chores (id, description, assigned_to, reward, recurrence, recurrence_weekdays,
+                            recurrence_interval, recurrence_day, recurrence_start, due_date,
+                            window_start, window_end, window_label, claimable, claim_hours, points,
+                            penalty, penalty_grace_minutes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
         "#,
     )
     .bind(id)
     .bind(&payload.description)
     .bind(payload.assigned_to)
     .bind(payload.reward)
+    .bind(payload.recurrence)
+    .bind(&payload.recurrence_weekdays)
+    .bind(payload.recurrence_interval)
+    .bind(payload.recurrence_day)
+    .bind(recurrence_start)
+    .bind(payload.due_date)
+    .bind(payload.window_start)
+    .bind(payload.window_end)
+    .bind(&payload.window_label)
+    .bind(payload.assigned_to.is_none())
+    .bind(payload.claim_hours)
+    .bind(payload.points)
+    .bind(payload.penalty)
+    .bind(payload.penalty_grace_minutes)
     .execute(&state.db)
     .await?;
 
@@ -92,6 +383,13 @@ pub async fn create_chore(
         .fetch_one(&state.db)
         .await?;
 
+    // Spawn today's occurrence right away instead of waiting for the next refresh
+    if chore.is_series()
+        && let Err(e) = background::generate_chore_occurrences(&state).await
+    {
+        tracing::warn!(error = ?e, "failed to generate chore occurrences");
+    }
+
     Ok(Json(chore))
 }
 
@@ -113,7 +411,8 @@ pub async fn update_chore(
         if chore.assigned_to != Some(auth.user_id) {
             return Err(AppError::AuthError);
         }
-        if payload.description.is_some() || payload.assigned_to.is_some() || payload.reward.is_some() {
+        // The assignee can only check the chore off, not move its deadline or schedule
+        if !payload.only_completion() {
             return Err(AppError::AuthError);
         }
     }
@@ -124,6 +423,46 @@ pub async fn update_chore(
         }
     }
 
+    let changes_recurrence = payload.recurrence.is_some()
+        || payload.recurrence_weekdays.is_some()
+        || payload.recurrence_interval.is_some()
+        || payload.recurrence_day.is_some()
+        || payload.recurrence_start.is_some();
+
+    if chore.is_series() {
+        if payload.completed.is_some() {
+            return Err(AppError::InvalidInput("Recurring chores are completed per occurrence".to_string()));
+        }
+        validate_recurrence(
+            payload.recurrence.or(chore.recurrence),
+            payload.recurrence_weekdays.as_deref().or(chore.recurrence_weekdays.as_deref()),
+            payload.recurrence_interval.or(chore.recurrence_interval),
+            payload.recurrence_day.or(chore.recurrence_day),
+        )?;
+    } else if changes_recurrence {
+        return Err(AppError::InvalidInput("Only recurring chores have a schedule".to_string()));
+    }
+
+    if chore.is_series() && payload.due_date.is_some() {
+        return Err(AppError::InvalidInput("Recurring chores are due on each occurrence date".to_string()));
+    }
+
+    validate_window(
+        payload.window_start.or(chore.window_start),
+        payload.window_end.or(chore.window_end),
+        payload.window_label.as_deref(),
+    )?;
+
+    if payload.claim_hours.is_some_and(|h| !(1..=720).contains(&h)) {
+        return Err(AppError::InvalidInput("Claim duration must be between 1 and 720 hours".to_string()));
+    }
+
+    validate_reward(payload.reward)?;
+
+    validate_points(payload.points)?;
+
+    validate_penalty(payload.penalty, payload.penalty_grace_minutes)?;
+
     // If reassigning, verify new user exists
     if let Some(new_assigned_to) = payload.assigned_to {
         let user_exists: bool = sqlx::query_scalar(
@@ -138,6 +477,8 @@ pub async fn update_chore(
         }
     }
 
+    let mut tx = state.db.begin().await?;
+
     sqlx::query(
         r#"
         UPDATE chores
@@ -145,24 +486,58 @@ pub async fn update_chore(
             description = COALESCE($1, description),
             assigned_to = COALESCE($2, assigned_to),
             reward = COALESCE($3, reward),
-            completed = COALESCE($4, completed),
+            recurrence = COALESCE($4, recurrence),
+            recurrence_weekdays = COALESCE($5, recurrence_weekdays),
+            recurrence_interval = COALESCE($6, recurrence_interval),
+            recurrence_day = COALESCE($7, recurrence_day),
+            recurrence_start = COALESCE($8, recurrence_start),
+            due_date = COALESCE($9, due_date),
+            window_start = COALESCE($10, window_start),
+            window_end = COALESCE($11, window_end),
+            window_label = COALESCE($12, window_label),
+            claim_hours = COALESCE($13, claim_hours),
+            points = COALESCE($14, points),
+            penalty = COALESCE($15, penalty),
+            penalty_grace_minutes = COALESCE($16, penalty_grace_minutes),
             updated_at = datetime('now')
-        WHERE id = $5
+        WHERE id = $17
         "#,
     )
     .bind(payload.description)
     .bind(payload.assigned_to)
     .bind(payload.reward)
-    .bind(payload.completed)
+    .bind(payload.recurrence)
+    .bind(payload.recurrence_weekdays)
+    .bind(payload.recurrence_interval)
+    .bind(payload.recurrence_day)
+    .bind(payload.recurrence_start)
+    .bind(payload.due_date)
+    .bind(payload.window_start)
+    .bind(payload.window_end)
+    .bind(payload.window_label)
+    .bind(payload.claim_hours)
+    .bind(payload.points)
+    .bind(payload.penalty)
+    .bind(payload.penalty_grace_minutes)
     .bind(id)
-    .execute(&state.db)
+    .execute(&mut *tx)
     .await?;
 
+    if let Some(completed) = payload.completed {
+        let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        request_completion(&mut tx, &chore, &auth, completed).await?;
+    }
+
     let updated_chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
         .bind(id)
-        .fetch_one(&state.db)
+        .fetch_one(&mut *tx)
         .await?;
 
+    tx.commit().await?;
+
     Ok(Json(updated_chore))
 }
 
@@ -190,11 +565,13 @@ pub async fn toggle_complete(
     Path(id): Path<Uuid>,
     auth: AuthUser,
 ) -> Result<Json<Chore>, AppError> {
+    let mut tx = state.db.begin().await?;
+
     let chore = query_as::<_, Chore>(
         "SELECT * FROM chores WHERE id = $1"
     )
     .bind(id)
-    .fetch_optional(&state.db)
+    .fetch_optional(&mut *tx)
     .await?
     .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
 
@@ -203,20 +580,898 @@ pub async fn toggle_complete(
         return Err(AppError::AuthError);
     }
 
-    sqlx::query(
-        r#"
-        UPDATE chores
-        SET completed = NOT completed, updated_at = datetime('now')
-        WHERE id = $1
-        "#,
+    if chore.is_series() {
+        return Err(AppError::InvalidInput("Recurring chores are completed per occurrence".to_string()));
+    }
+
+    let done = chore.completed || chore.review_status == Some(ReviewStatus::Pending);
+
+    request_completion(&mut tx, &chore, &auth, !done).await?;
+
+    let updated_chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(updated_chore))
+}
+
+pub async fn display_complete_chore(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    kiosk: DisplayAuth,
+    Json(payload): Json<DisplayCompleteChoreSchema>,
+) -> Result<Json<Chore>, AppError> {
+    let chore = query_as::<_, Chore>(
+        "SELECT * FROM chores WHERE id = $1"
     )
     .bind(id)
-    .execute(&state.db)
-    .await?;
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    if chore.is_series() {
+        return Err(AppError::InvalidInput("Recurring chores are completed per occurrence".to_string()));
+    }
+
+    // Displays only check chores off, so a repeated or replayed request can't undo one
+    if chore.completed || chore.review_status == Some(ReviewStatus::Pending) {
+        return Err(AppError::InvalidInput("Chore is already done".to_string()));
+    }
+
+    let assignee = chore
+        .assigned_to
+        .ok_or(AppError::InvalidInput("Chore is not assigned".to_string()))?;
+
+    let role = verify_display_pin(&state, kiosk.token_id, assignee, &payload.pin).await?;
+
+    // Completing from a display is the same as the assignee checking it off
+    // themselves, so children still go through parent review
+    let auth = AuthUser { user_id: assignee, role };
+
+    let mut tx = state.db.begin().await?;
+
+    request_completion(&mut tx, &chore, &auth, true).await?;
+
     let updated_chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
         .bind(id)
-        .fetch_one(&state.db)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    tracing::debug!(display_id = %kiosk.token_id, chore_id = %id, "Chore completed from display");
+
+    Ok(Json(updated_chore))
+}
+
+/// Largest proof photo accepted, in bytes
+pub const MAX_PROOF_BYTES: usize = 10 * 1024 * 1024;
+
+/// Check a proof photo can be attached to the chore by this user
+fn check_proof_upload(chore: &Chore, auth: &AuthUser) -> Result<(), AppError> {
+    // Only admin or assigned user can attach proof
+    if !auth.is_admin() && chore.assigned_to != Some(auth.user_id) {
+        return Err(AppError::AuthError);
+    }
+
+    if chore.is_series() {
+        return Err(AppError::InvalidInput("Recurring chores are completed per occurrence".to_string()));
+    }
+
+    if chore.completed {
+        return Err(AppError::InvalidInput("Chore is already complete".to_string()));
+    }
+
+    Ok(())
+}
+
+pub async fn upload_chore_proof(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    mut multipart: Multipart,
+) -> Result<Json<Chore>, AppError> {
+    // Checked before reading the upload, and again once the transaction starts
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    check_proof_upload(&chore, &auth)?;
+
+    let mut photo = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.body_text()))?
+    {
+        if field.name() == Some("photo") {
+            let bytes = field.bytes().await.map_err(|e| AppError::BadRequest(e.body_text()))?;
+            photo = Some(bytes);
+        }
+    }
+
+    let photo = photo.ok_or(AppError::InvalidInput("Missing photo".to_string()))?;
+    let extension = image_extension(&photo)
+        .ok_or(AppError::InvalidInput("Photo must be a JPEG or PNG".to_string()))?;
+
+    let dir = state.photos_dir.join(CHORE_PROOFS_DIR);
+    let filename = format!("{}-{}.{}", chore.id, Uuid::new_v4().simple(), extension);
+    let file_path = photo_path(&dir, &filename)?;
+
+    tokio::fs::create_dir_all(&dir).await
+        .map_err(|_| AppError::BadRequest("Failed to save photo".to_string()))?;
+    tokio::fs::write(&file_path, &photo).await
+        .map_err(|_| AppError::BadRequest("Failed to save photo".to_string()))?;
+
+    let saved: Result<(Chore, Option<String>), AppError> = async {
+        let mut tx = state.db.begin().await?;
+
+        let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+        check_proof_upload(&chore, &auth)?;
+
+        sqlx::query(
+            r#"
+            UPDATE chores SET proof_photo = $1, proof_uploaded_at = datetime('now'), updated_at = datetime('now')
+            WHERE id = $2
+            "#,
+        )
+        .bind(&filename)
+        .bind(id)
+        .execute(&mut *tx)
         .await?;
 
+        // Attaching proof also marks the chore done, which sends a child's chore for review
+        if chore.review_status != Some(ReviewStatus::Pending) {
+            request_completion(&mut tx, &chore, &auth, true).await?;
+        }
+
+        let updated_chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok((updated_chore, chore.proof_photo))
+    }
+    .await;
+
+    let (updated_chore, replaced) = match saved {
+        Ok(saved) => saved,
+        Err(e) => {
+            // Nothing points at the new photo, so it isn't left behind
+            let _ = tokio::fs::remove_file(&file_path).await;
+            return Err(e);
+        }
+    };
+
+    // A replaced photo is no longer referenced by anything
+    if let Some(old) = replaced
+        && let Ok(old_path) = photo_path(&dir, &old)
+    {
+        let _ = tokio::fs::remove_file(old_path).await;
+    }
+
     Ok(Json(updated_chore))
-}
\ No newline at end of file
+}
+
+pub async fn get_chore_proof(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    if !auth.is_admin() && chore.assigned_to != Some(auth.user_id) {
+        return Err(AppError::AuthError);
+    }
+
+    let filename = chore
+        .proof_photo
+        .ok_or(AppError::BadRequest("Photo not found".to_string()))?;
+    let file_path = photo_path(&state.photos_dir.join(CHORE_PROOFS_DIR), &filename)?;
+
+    let bytes: Vec<u8> = tokio::fs::read(&file_path).await
+        .map_err(|_| AppError::BadRequest("Photo not found".to_string()))?;
+
+    Ok((
+        [(header::CONTENT_TYPE, photo_content_type(&filename))],
+        bytes,
+    ))
+}
+
+pub async fn waive_penalty(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Chore>, AppError> {
+    require_admin(&auth)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    if chore.penalty_waived_at.is_some() {
+        return Err(AppError::InvalidInput("Penalty already waived".to_string()));
+    }
+
+    let penalty_entry = match chore.penalty_entry_id {
+        Some(entry_id) => query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger WHERE id = $1 AND voided_at IS NULL")
+            .bind(entry_id)
+            .fetch_optional(&mut *tx)
+            .await?,
+        None => None,
+    }
+    .ok_or(AppError::InvalidInput("No penalty has been charged for this chore".to_string()))?;
+
+    // The ledger is append-only, so waiving pays the penalty back
+    post_entry(&mut tx, NewLedgerEntry {
+        user_id: penalty_entry.user_id,
+        amount: -penalty_entry.amount,
+        description: format!("Penalty waived: {}", chore.description),
+        chore_id: Some(chore.id),
+        reverses: Some(penalty_entry.id),
+        ..Default::default()
+    }).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE chores
+        SET penalty_waived_by = $1, penalty_waived_at = datetime('now'), updated_at = datetime('now')
+        WHERE id = $2
+        "#,
+    )
+    .bind(auth.user_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let updated_chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(updated_chore))
+}
+
+pub async fn list_pending_reviews(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChoreWithUser>>, AppError> {
+    require_admin(&auth)?;
+
+    let chores = query_as::<_, ChoreWithUser>(
+        r#"
+        SELECT c.*, u.name as assigned_name, r.name as reviewer_name
+        FROM chores c
+        LEFT JOIN users u ON c.assigned_to = u.id
+        LEFT JOIN users r ON c.reviewed_by = r.id
+        WHERE c.review_status = 'pending'
+        ORDER BY c.updated_at ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(chores))
+}
+
+pub async fn approve_chore(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReviewChoreSchema>,
+) -> Result<Json<Chore>, AppError> {
+    review_chore(&state, id, &auth, payload, ReviewStatus::Approved).await
+}
+
+pub async fn reject_chore(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReviewChoreSchema>,
+) -> Result<Json<Chore>, AppError> {
+    review_chore(&state, id, &auth, payload, ReviewStatus::Rejected).await
+}
+
+async fn review_chore(
+    state: &AppState,
+    id: Uuid,
+    auth: &AuthUser,
+    payload: ReviewChoreSchema,
+    status: ReviewStatus,
+) -> Result<Json<Chore>, AppError> {
+    require_admin(auth)?;
+
+    if payload.note.as_ref().is_some_and(|n| n.len() > 500) {
+        return Err(AppError::InvalidInput("Note too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    if chore.review_status != Some(ReviewStatus::Pending) {
+        return Err(AppError::InvalidInput("Chore is not awaiting review".to_string()));
+    }
+
+    if status == ReviewStatus::Approved {
+        set_completed(&mut tx, &chore, true, auth.user_id).await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE chores
+        SET review_status = $1, reviewed_by = $2, reviewed_at = datetime('now'), review_note = $3
+        WHERE id = $4
+        "#,
+    )
+    .bind(status)
+    .bind(auth.user_id)
+    .bind(payload.note)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let updated_chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(updated_chore))
+}
+
+async fn rotation_members(
+    conn: &mut SqliteConnection,
+    chore_id: Uuid,
+) -> Result<Vec<RotationMember>, AppError> {
+    let members = query_as::<_, RotationMember>(
+        r#"
+        SELECT m.position, m.user_id, u.name
+        FROM chore_rotation_members m
+        JOIN users u ON m.user_id = u.id
+        WHERE m.chore_id = $1
+        ORDER BY m.position ASC
+        "#
+    )
+    .bind(chore_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(members)
+}
+
+/// Load a chore the user may see the rotation of, along with its members
+async fn load_rotation(
+    conn: &mut SqliteConnection,
+    id: Uuid,
+    auth: &AuthUser,
+) -> Result<(Chore, Vec<RotationMember>), AppError> {
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    let members = rotation_members(&mut *conn, id).await?;
+
+    // Admins and anyone taking part in the rotation can view it
+    if !auth.is_admin()
+        && chore.assigned_to != Some(auth.user_id)
+        && !members.iter().any(|m| m.user_id == auth.user_id)
+    {
+        return Err(AppError::AuthError);
+    }
+
+    Ok((chore, members))
+}
+
+pub async fn get_rotation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<ChoreRotation>, AppError> {
+    let mut conn = state.db.acquire().await?;
+    let (chore, members) = load_rotation(&mut conn, id, &auth).await?;
+
+    Ok(Json(ChoreRotation {
+        chore_id: chore.id,
+        period_days: chore.rotation_period_days,
+        start: chore.rotation_start,
+        members,
+    }))
+}
+
+pub async fn update_rotation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateRotationSchema>,
+) -> Result<Json<ChoreRotation>, AppError> {
+    require_admin(&auth)?;
+
+    if payload.user_ids.len() > 20 {
+        return Err(AppError::InvalidInput("Too many rotation members".to_string()));
+    }
+
+    if !(1..=365).contains(&payload.period_days) {
+        return Err(AppError::InvalidInput("Rotation period must be between 1 and 365 days".to_string()));
+    }
+
+    let unique: std::collections::HashSet<_> = payload.user_ids.iter().collect();
+    if unique.len() != payload.user_ids.len() {
+        return Err(AppError::InvalidInput("Rotation members must be unique".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    sqlx::query("DELETE FROM chore_rotation_members WHERE chore_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    for (position, user_id) in payload.user_ids.iter().enumerate() {
+        let user_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+        )
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if !user_exists {
+            return Err(AppError::InvalidInput("Rotation member not found".to_string()));
+        }
+
+        sqlx::query(
+            "INSERT INTO chore_rotation_members (chore_id, position, user_id) VALUES ($1, $2, $3)"
+        )
+        .bind(id)
+        .bind(position as i64)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // An empty member list turns rotation off and leaves the current assignee alone
+    let (period_days, start) = if payload.user_ids.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(payload.period_days),
+            Some(payload.start.unwrap_or_else(|| Local::now().date_naive())),
+        )
+    };
+
+    let rotating = Chore {
+        rotation_period_days: period_days,
+        rotation_start: start,
+        ..chore
+    };
+    let assigned_to = rotating
+        .rotation_slot(Local::now().date_naive(), payload.user_ids.len())
+        .map(|slot| payload.user_ids[slot])
+        .or(rotating.assigned_to);
+
+    sqlx::query(
+        r#"
+        UPDATE chores
+        SET rotation_period_days = $1, rotation_start = $2, assigned_to = $3, updated_at = datetime('now')
+        WHERE id = $4
+        "#,
+    )
+    .bind(period_days)
+    .bind(start)
+    .bind(assigned_to)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let members = rotation_members(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ChoreRotation {
+        chore_id: id,
+        period_days,
+        start,
+        members,
+    }))
+}
+
+pub async fn preview_rotation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<RotationPreviewQuery>,
+    auth: AuthUser,
+) -> Result<Json<RotationPreview>, AppError> {
+    let mut conn = state.db.acquire().await?;
+    let (chore, members) = load_rotation(&mut conn, id, &auth).await?;
+
+    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
+    let member = chore
+        .rotation_slot(date, members.len())
+        .and_then(|slot| members.into_iter().nth(slot));
+
+    // Chores without a rotation always belong to their current assignee
+    let (user_id, name) = match member {
+        Some(m) => (Some(m.user_id), Some(m.name)),
+        None => {
+            let name: Option<String> = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+                .bind(chore.assigned_to)
+                .fetch_optional(&mut *conn)
+                .await?;
+            (chore.assigned_to, name)
+        }
+    };
+
+    Ok(Json(RotationPreview { date, user_id, name }))
+}
+
+/// Apply a completion change requested by a user.
+///
+/// A child's completion is held as pending until a parent approves it, and
+/// toggling again while pending withdraws the request. Everyone else
+/// completes chores directly, which clears any outstanding review.
+async fn request_completion(
+    conn: &mut SqliteConnection,
+    chore: &Chore,
+    auth: &AuthUser,
+    completed: bool,
+) -> Result<(), AppError> {
+    if auth.role != UserRole::Child {
+        set_completed(&mut *conn, chore, completed, auth.user_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE chores
+            SET review_status = NULL, reviewed_by = NULL, reviewed_at = NULL, review_note = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(chore.id)
+        .execute(&mut *conn)
+        .await?;
+
+        return Ok(());
+    }
+
+    if chore.completed {
+        return Err(AppError::InvalidInput("Approved chores can only be changed by a parent".to_string()));
+    }
+
+    if !completed && chore.review_status != Some(ReviewStatus::Pending) {
+        return Ok(());
+    }
+
+    let status = completed.then_some(ReviewStatus::Pending);
+
+    sqlx::query(
+        r#"
+        UPDATE chores
+        SET review_status = $1, reviewed_by = NULL, reviewed_at = NULL, review_note = NULL,
+            updated_at = datetime('now')
+        WHERE id = $2
+        "#,
+    )
+    .bind(status)
+    .bind(chore.id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Mark a chore complete or incomplete, paying out or reversing its reward.
+///
+/// Completing a chore with a reward credits the assignee's allowance ledger;
+/// un-completing it posts a reversing entry for whatever was paid. Every
+/// change is recorded in the chore's event history.
+async fn set_completed(
+    conn: &mut SqliteConnection,
+    chore: &Chore,
+    completed: bool,
+    actor_id: Uuid,
+) -> Result<(), AppError> {
+    if chore.completed == completed {
+        return Ok(());
+    }
+
+    let mut reward_entry_id = chore.reward_entry_id;
+    let mut points_entry_id = chore.points_entry_id;
+
+    if completed {
+        if let (Some(user_id), Some(reward)) = (chore.assigned_to, chore.reward.filter(|r| *r > 0)) {
+            let entry = post_entry(&mut *conn, NewLedgerEntry {
+                user_id,
+                amount: reward,
+                description: format!("Chore reward: {} (chore {})", chore.description, chore.id),
+                chore_id: Some(chore.id),
+                ..Default::default()
+            }).await?;
+            reward_entry_id = Some(entry.id);
+        }
+
+        if let (Some(user_id), Some(points)) = (chore.assigned_to, chore.points.filter(|p| *p > 0)) {
+            let entry = post_points_entry(&mut *conn, NewPointsEntry {
+                user_id,
+                amount: points,
+                description: format!("Chore points: {} (chore {})", chore.description, chore.id),
+                chore_id: Some(chore.id),
+            }).await?;
+            points_entry_id = Some(entry.id);
+        }
+    } else {
+        if let Some(entry_id) = reward_entry_id.take() {
+            let paid = query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger WHERE id = $1 AND voided_at IS NULL")
+                .bind(entry_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+
+            if let Some(paid) = paid {
+                post_entry(&mut *conn, NewLedgerEntry {
+                    user_id: paid.user_id,
+                    amount: -paid.amount,
+                    description: format!("Chore reward reversed: {} (chore {})", chore.description, chore.id),
+                    chore_id: Some(chore.id),
+                    reverses: Some(paid.id),
+                    ..Default::default()
+                }).await?;
+            }
+        }
+
+        if let Some(entry_id) = points_entry_id.take() {
+            let awarded = query_as::<_, PointsTransaction>("SELECT * FROM points_ledger WHERE id = $1")
+                .bind(entry_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+
+            if let Some(awarded) = awarded {
+                post_points_entry(&mut *conn, NewPointsEntry {
+                    user_id: awarded.user_id,
+                    amount: -awarded.amount,
+                    description: format!("Chore points reversed: {} (chore {})", chore.description, chore.id),
+                    chore_id: Some(chore.id),
+                }).await?;
+            }
+        }
+    }
+
+    // Only flip from the state we read, so overlapping requests can't both pay out;
+    // bailing rolls back the entries posted above
+    let result = sqlx::query(
+        r#"
+        UPDATE chores
+        SET completed = $1, reward_entry_id = $2, points_entry_id = $3, updated_at = datetime('now')
+        WHERE id = $4 AND completed = $5
+        "#,
+    )
+    .bind(completed)
+    .bind(reward_entry_id)
+    .bind(points_entry_id)
+    .bind(chore.id)
+    .bind(chore.completed)
+    .execute(&mut *conn)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Chore was changed by someone else, try again".to_string()));
+    }
+
+    let (event_type, on_time) = if completed {
+        let now = Local::now().naive_local();
+        let on_time = due_deadline(chore.due_date, chore.window_end).map(|due| now <= due);
+        (ChoreEventType::Completed, on_time)
+    } else {
+        (ChoreEventType::Uncompleted, None)
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO chore_events (id, chore_id, chore_ref, user_id, actor_id, event_type, on_time)
+        VALUES ($1, $2, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(chore.id)
+    .bind(chore.assigned_to)
+    .bind(actor_id)
+    .bind(event_type)
+    .bind(on_time)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_chore_history(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChoreEvent>>, AppError> {
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    if !auth.is_admin() && chore.assigned_to != Some(auth.user_id) {
+        return Err(AppError::AuthError);
+    }
+
+    let events = query_as::<_, ChoreEvent>(
+        "SELECT * FROM chore_events WHERE chore_id = $1 ORDER BY seq DESC"
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(events))
+}
+
+pub async fn get_chore_stats(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ChoreStatsQuery>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChoreStats>>, AppError> {
+    let to = query.to.unwrap_or_else(|| Local::now().date_naive());
+    let from = query.from.unwrap_or(to - Duration::days(29));
+
+    if from > to {
+        return Err(AppError::InvalidInput("Start date must not be after end date".to_string()));
+    }
+
+    // Users can view their own statistics, admins can view anyone
+    let user_filter = if auth.is_admin() {
+        query.user_id
+    } else {
+        if query.user_id.is_some_and(|id| id != auth.user_id) {
+            return Err(AppError::AuthError);
+        }
+        Some(auth.user_id)
+    };
+
+    let users: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT id, name FROM users WHERE ($1 IS NULL OR id = $1) ORDER BY name"
+    )
+    .bind(user_filter)
+    .fetch_all(&state.db)
+    .await?;
+
+    // Keyed by chore_ref so completions of chores deleted since still count; events
+    // from before chore_ref was recorded whose chore is gone each stand alone
+    let events: Vec<(Uuid, Uuid, ChoreEventType, Option<bool>, NaiveDate)> = sqlx::query_as(
+        r#"
+        SELECT e.user_id, COALESCE(e.chore_ref, e.id), e.event_type, e.on_time, date(e.created_at, 'localtime') as day
+        FROM chore_events e
+        JOIN users u ON e.user_id = u.id
+        WHERE date(e.created_at, 'localtime') BETWEEN $1 AND $2
+          AND ($3 IS NULL OR e.user_id = $3)
+          AND e.event_type IN ('completed', 'uncompleted')
+        ORDER BY e.seq ASC
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(user_filter)
+    .fetch_all(&state.db)
+    .await?;
+
+    // Only the latest event per chore counts, so a completion that was undone is ignored
+    let mut latest: HashMap<(Uuid, Uuid), (ChoreEventType, Option<bool>, NaiveDate)> = HashMap::new();
+    for (user_id, chore_id, event_type, on_time, day) in events {
+        latest.insert((user_id, chore_id), (event_type, on_time, day));
+    }
+
+    // Rewards still standing for chores done in the range; penalties and reversals aren't earnings
+    let earnings: HashMap<Uuid, i64> = sqlx::query_as::<_, (Uuid, i64)>(
+        r#"
+        SELECT l.user_id, SUM(l.amount)
+        FROM allowance_ledger l
+        JOIN chores c ON c.reward_entry_id = l.id
+        WHERE l.voided_at IS NULL
+          AND date(l.created_at, 'localtime') BETWEEN $1 AND $2
+          AND ($3 IS NULL OR l.user_id = $3)
+        GROUP BY l.user_id
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(user_filter)
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .collect();
+
+    let mut stats = Vec::with_capacity(users.len());
+
+    for (user_id, name) in users {
+        let completions: Vec<_> = latest
+            .iter()
+            .filter(|((uid, _), (event_type, _, _))| *uid == user_id && *event_type == ChoreEventType::Completed)
+            .map(|(_, (_, on_time, day))| (*on_time, *day))
+            .collect();
+
+        let on_time = completions.iter().filter(|(o, _)| *o == Some(true)).count() as i64;
+        let late = completions.iter().filter(|(o, _)| *o == Some(false)).count() as i64;
+        let days: BTreeSet<NaiveDate> = completions.iter().map(|(_, day)| *day).collect();
+        let (current_streak, longest_streak) = streaks(&days, to);
+
+        let earnings = earnings.get(&user_id).copied().unwrap_or(0);
+
+        stats.push(ChoreStats {
+            user_id,
+            name,
+            from,
+            to,
+            completed: completions.len() as i64,
+            on_time,
+            late,
+            on_time_rate: (on_time + late > 0).then(|| on_time as f64 / (on_time + late) as f64),
+            current_streak,
+            longest_streak,
+            earnings,
+        });
+    }
+
+    Ok(Json(stats))
+}
+
+/// Current and longest runs of consecutive days with at least one completion.
+///
+/// The current streak counts back from `end`, or from the day before if
+/// nothing has been done on `end` yet.
+fn streaks(days: &BTreeSet<NaiveDate>, end: NaiveDate) -> (i64, i64) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for day in days {
+        run = match previous {
+            Some(p) if p.succ_opt() == Some(*day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+
+    let mut day = if days.contains(&end) { end } else { end - Duration::days(1) };
+    let mut current = 0;
+    while days.contains(&day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+
+    (current, longest)
+}
@@ -1,12 +1,12 @@
 use axum::{
     extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    http::StatusCode,
     Json,
 };
 use std::sync::Arc;
 use sqlx::query_as;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{Local, Utc};
 use rand::Rng;
 
 use crate::{
@@ -16,10 +16,15 @@ use crate::{
         user::UserBalance,
         calendar::CalendarPublic,
         chore::ChoreWithUser,
+        reward::UserPoints,
+        routine::Routine,
+        away::is_away,
+        goal::SavingsGoal,
     },
+    handlers::{away::load_away_periods, goal::goal_progress, routine::routine_progress},
     state::{AppState, CachedPhotos},
-    utils::auth_helpers::{require_admin, generate_random_token},
-    middleware::auth::AuthUser,
+    utils::{auth_helpers::{require_admin, generate_random_token}, ledger::with_buckets},
+    middleware::auth::{AuthUser, DisplayAuth},
 };
 
 pub async fn list_tokens(
@@ -81,19 +86,8 @@ pub async fn delete_token(
 
 pub async fn get_display_data(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
+    _display: DisplayAuth,
 ) -> Result<Json<DisplayData>, AppError> {
-    let token_header = headers.get("X-Display-Token")
-        .ok_or(AppError::AuthError)?
-        .to_str()
-        .map_err(|_| AppError::AuthError)?;
-
-    let _token_record = query_as::<_, DisplayToken>("SELECT * FROM display_tokens WHERE token = $1")
-        .bind(token_header)
-        .fetch_optional(&state.db)
-        .await?
-        .ok_or(AppError::AuthError)?;
-
     let weather: Option<String> = sqlx::query_scalar(
         "SELECT data FROM weather_cache LIMIT 1",
     )
@@ -112,7 +106,7 @@ pub async fn get_display_data(
         SELECT u.id as user_id, u.name, COALESCE(
             (SELECT balance FROM allowance_ledger 
              WHERE user_id = u.id 
-             ORDER BY created_at DESC 
+             ORDER BY seq DESC 
              LIMIT 1), 0) as balance
         FROM users u
         WHERE u.track_allowance = 1
@@ -122,19 +116,83 @@ pub async fn get_display_data(
     .fetch_all(&state.db)
     .await?;
 
+    let mut conn = state.db.acquire().await?;
+    let allowances = with_buckets(&mut conn, allowances).await?;
+    drop(conn);
+
+    let points = query_as::<_, UserPoints>(
+        r#"
+        SELECT u.id as user_id, u.name, COALESCE(
+            (SELECT balance FROM points_ledger
+             WHERE user_id = u.id
+             ORDER BY seq DESC
+             LIMIT 1), 0) as points
+        FROM users u
+        WHERE u.role = 'child' OR EXISTS(SELECT 1 FROM points_ledger WHERE user_id = u.id)
+        ORDER BY u.name
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
     let chores = query_as::<_, ChoreWithUser>(
         r#"
-        SELECT c.id, c.description, c.assigned_to, u.name as assigned_name,
-               c.reward, c.completed, c.created_at, c.updated_at
+        SELECT c.*, u.name as assigned_name, r.name as reviewer_name
         FROM chores c
         JOIN users u ON c.assigned_to = u.id
-        WHERE c.completed = 0
+        LEFT JOIN users r ON c.reviewed_by = r.id
+        WHERE c.completed = 0 AND c.recurrence IS NULL
         ORDER BY u.name ASC, c.created_at ASC
         "#
     )
     .fetch_all(&state.db)
     .await?;
 
+    let open_chores = query_as::<_, ChoreWithUser>(
+        r#"
+        SELECT c.*, NULL as assigned_name, NULL as reviewer_name
+        FROM chores c
+        WHERE c.claimable = 1 AND c.assigned_to IS NULL AND c.completed = 0 AND c.recurrence IS NULL
+        ORDER BY c.reward DESC, c.created_at ASC
+        "#
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut conn = state.db.acquire().await?;
+    let away = load_away_periods(&mut conn).await?;
+    drop(conn);
+
+    // Chores of anyone away today are hidden until they're back
+    let now = Local::now().naive_local();
+    let chores = chores
+        .into_iter()
+        .filter(|c| c.assigned_to.is_none_or(|user_id| !is_away(&away, user_id, now.date())))
+        .map(|c| c.with_overdue(now, &away))
+        .collect();
+    let open_chores = open_chores.into_iter().map(|c| c.with_overdue(now, &away)).collect();
+
+    // Only routines inside their time window are shown on the display
+    let routines = query_as::<_, Routine>(
+        "SELECT * FROM routines WHERE active = 1 ORDER BY window_start ASC, name ASC"
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .filter(|r| r.in_window(now.time()))
+    .collect();
+
+    let mut conn = state.db.acquire().await?;
+    let routines = routine_progress(&mut conn, routines, now.date(), now.time()).await?;
+
+    let goals = query_as::<_, SavingsGoal>(
+        "SELECT * FROM savings_goals WHERE active = 1 ORDER BY created_at ASC"
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    let goals = goal_progress(&mut conn, goals, now.date()).await?;
+    drop(conn);
+
     let mut background_url = None;
 
     let picked_items_json: Option<String> = sqlx::query_scalar(
@@ -218,7 +276,11 @@ pub async fn get_display_data(
         weather: weather_json,
         calendars,
         allowances,
+        points,
         chores,
+        open_chores,
+        routines,
+        goals,
         background_url,
     }))
 }
@@ -0,0 +1,346 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use chrono::{Local, NaiveDate, TimeDelta};
+use sqlx::{query_as, SqliteConnection};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::goal::{CreateGoalSchema, GoalProgress, ListGoalsQuery, SavingsGoal, UpdateGoalSchema},
+    state::AppState,
+    middleware::auth::AuthUser,
+};
+
+/// Days of ledger history used to project when a goal will be reached
+const PROJECTION_WINDOW_DAYS: i64 = 30;
+
+/// Largest goal that can be set, in cents
+const MAX_TARGET_AMOUNT: i64 = 100_000_000;
+
+fn validate_goal(
+    name: Option<&str>,
+    target_amount: Option<i64>,
+    image_url: Option<&str>,
+) -> Result<(), AppError> {
+    if name.is_some_and(|n| n.trim().is_empty() || n.len() > 200) {
+        return Err(AppError::InvalidInput("Name must be between 1 and 200 characters".to_string()));
+    }
+
+    if target_amount.is_some_and(|t| t <= 0 || t > MAX_TARGET_AMOUNT) {
+        return Err(AppError::InvalidInput("Target must be positive and at most 1,000,000.00".to_string()));
+    }
+
+    if let Some(url) = image_url.filter(|u| !u.is_empty()) {
+        if url.len() > 2048 {
+            return Err(AppError::InvalidInput("Image URL too long".to_string()));
+        }
+        let parsed_url = Url::parse(url)
+            .map_err(|_| AppError::InvalidInput("Invalid URL format".to_string()))?;
+        if parsed_url.scheme() != "https" {
+            return Err(AppError::InvalidInput("Only HTTPS URLs are allowed".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a bucket belongs to the user the goal is for
+async fn validate_bucket(
+    conn: &mut SqliteConnection,
+    user_id: Uuid,
+    bucket_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let Some(bucket_id) = bucket_id else {
+        return Ok(());
+    };
+
+    let owned: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM allowance_buckets WHERE id = $1 AND user_id = $2)"
+    )
+        .bind(bucket_id)
+        .bind(user_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if !owned {
+        return Err(AppError::InvalidInput("Bucket not found".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Work out how far along each goal is and when it should be reached.
+///
+/// The projection assumes saving continues at the net rate of the last
+/// `PROJECTION_WINDOW_DAYS` days; goals that aren't growing get no date.
+pub async fn goal_progress(
+    conn: &mut SqliteConnection,
+    goals: Vec<SavingsGoal>,
+    today: NaiveDate,
+) -> Result<Vec<GoalProgress>, AppError> {
+    let mut progress = Vec::with_capacity(goals.len());
+
+    for goal in goals {
+        let user_name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+            .bind(goal.user_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let (bucket_name, saved, recent) = match goal.bucket_id {
+            Some(bucket_id) => {
+                let bucket_name: Option<String> = sqlx::query_scalar(
+                    "SELECT name FROM allowance_buckets WHERE id = $1"
+                )
+                    .bind(bucket_id)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+
+                let (saved, recent): (i64, i64) = sqlx::query_as(
+                    r#"
+                    SELECT COALESCE(SUM(a.amount), 0),
+                           COALESCE(SUM(CASE WHEN l.created_at >= datetime('now', '-' || $2 || ' days')
+                                             THEN a.amount ELSE 0 END), 0)
+                    FROM allowance_allocations a
+                    JOIN allowance_ledger l ON a.entry_id = l.id
+                    WHERE a.bucket_id = $1
+                    "#
+                )
+                    .bind(bucket_id)
+                    .bind(PROJECTION_WINDOW_DAYS)
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                (bucket_name, saved, recent)
+            }
+            None => {
+                let saved: Option<i64> = sqlx::query_scalar(
+                    "SELECT balance FROM allowance_ledger WHERE user_id = $1 ORDER BY seq DESC LIMIT 1"
+                )
+                    .bind(goal.user_id)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+
+                let recent: i64 = sqlx::query_scalar(
+                    r#"
+                    SELECT COALESCE(SUM(amount), 0) FROM allowance_ledger
+                    WHERE user_id = $1 AND created_at >= datetime('now', '-' || $2 || ' days')
+                    "#
+                )
+                    .bind(goal.user_id)
+                    .bind(PROJECTION_WINDOW_DAYS)
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                (None, saved.unwrap_or(0), recent)
+            }
+        };
+
+        let reached = saved >= goal.target_amount;
+        let progress_percent = (saved.max(0) as f64 * 100.0 / goal.target_amount.max(1) as f64).min(100.0);
+        let progress_percent = (progress_percent * 10.0).round() / 10.0;
+
+        let projected_date = if reached {
+            None
+        } else if recent > 0 {
+            // Whole days at the recent daily rate, rounded up; too far off to date is None
+            goal.target_amount
+                .checked_sub(saved)
+                .and_then(|remaining| remaining.checked_mul(PROJECTION_WINDOW_DAYS))
+                .and_then(|scaled| scaled.checked_add(recent - 1))
+                .map(|scaled| scaled / recent)
+                .and_then(TimeDelta::try_days)
+                .and_then(|days| today.checked_add_signed(days))
+        } else {
+            None
+        };
+
+        let on_track = match (goal.target_date, projected_date) {
+            _ if reached => Some(true),
+            (Some(target), Some(projected)) => Some(projected <= target),
+            (Some(_), None) => Some(false),
+            (None, _) => None,
+        };
+
+        progress.push(GoalProgress {
+            goal,
+            user_name,
+            bucket_name,
+            saved,
+            progress_percent,
+            reached,
+            projected_date,
+            on_track,
+        });
+    }
+
+    Ok(progress)
+}
+
+async fn fetch_progress(conn: &mut SqliteConnection, id: Uuid) -> Result<GoalProgress, AppError> {
+    let goal = query_as::<_, SavingsGoal>("SELECT * FROM savings_goals WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::InvalidInput("Goal not found".to_string()))?;
+
+    let mut progress = goal_progress(&mut *conn, vec![goal], Local::now().date_naive()).await?;
+
+    Ok(progress.remove(0))
+}
+
+pub async fn list_goals(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListGoalsQuery>,
+    auth: AuthUser,
+) -> Result<Json<Vec<GoalProgress>>, AppError> {
+    // Users see their own goals, admins see everyone's
+    let goals = query_as::<_, SavingsGoal>(
+        r#"
+        SELECT * FROM savings_goals
+        WHERE ($1 OR user_id = $2) AND ($3 IS NULL OR user_id = $3)
+        ORDER BY active DESC, created_at ASC
+        "#
+    )
+    .bind(auth.is_admin())
+    .bind(auth.user_id)
+    .bind(query.user_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut conn = state.db.acquire().await?;
+    let progress = goal_progress(&mut conn, goals, Local::now().date_naive()).await?;
+
+    Ok(Json(progress))
+}
+
+pub async fn create_goal(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateGoalSchema>,
+) -> Result<Json<GoalProgress>, AppError> {
+    // Users can set their own goals, admins can set anyone's
+    if !auth.is_admin() && auth.user_id != payload.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    validate_goal(Some(&payload.name), Some(payload.target_amount), payload.image_url.as_deref())?;
+
+    let mut tx = state.db.begin().await?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(payload.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    validate_bucket(&mut tx, payload.user_id, payload.bucket_id).await?;
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO savings_goals (id, user_id, bucket_id, name, target_amount, image_url, target_date)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#
+    )
+    .bind(id)
+    .bind(payload.user_id)
+    .bind(payload.bucket_id)
+    .bind(payload.name.trim())
+    .bind(payload.target_amount)
+    .bind(payload.image_url.filter(|u| !u.is_empty()))
+    .bind(payload.target_date)
+    .execute(&mut *tx)
+    .await?;
+
+    let progress = fetch_progress(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(progress))
+}
+
+pub async fn update_goal(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateGoalSchema>,
+) -> Result<Json<GoalProgress>, AppError> {
+    validate_goal(payload.name.as_deref(), payload.target_amount, payload.image_url.as_deref())?;
+
+    let mut tx = state.db.begin().await?;
+
+    let goal = query_as::<_, SavingsGoal>("SELECT * FROM savings_goals WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Goal not found".to_string()))?;
+
+    if !auth.is_admin() && auth.user_id != goal.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    validate_bucket(&mut tx, goal.user_id, payload.bucket_id).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE savings_goals
+        SET
+            bucket_id = COALESCE($1, bucket_id),
+            name = COALESCE($2, name),
+            target_amount = COALESCE($3, target_amount),
+            image_url = COALESCE($4, image_url),
+            target_date = COALESCE($5, target_date),
+            active = COALESCE($6, active)
+        WHERE id = $7
+        "#
+    )
+    .bind(payload.bucket_id)
+    .bind(payload.name.as_deref().map(str::trim))
+    .bind(payload.target_amount)
+    .bind(payload.image_url)
+    .bind(payload.target_date)
+    .bind(payload.active)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let progress = fetch_progress(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(progress))
+}
+
+pub async fn delete_goal(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    let owner: Option<Uuid> = sqlx::query_scalar("SELECT user_id FROM savings_goals WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let owner = owner.ok_or(AppError::InvalidInput("Goal not found".to_string()))?;
+
+    if !auth.is_admin() && auth.user_id != owner {
+        return Err(AppError::AuthError);
+    }
+
+    sqlx::query("DELETE FROM savings_goals WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
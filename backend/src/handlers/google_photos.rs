@@ -17,6 +17,7 @@ use crate::{
     middleware::auth::AuthUser,
     utils::google_oauth::{self, PickerSession},
     utils::jwt::verify_jwt,
+    utils::photo_files::{photo_content_type, photo_path},
 };
 
 #[derive(Debug, Deserialize)]
@@ -65,38 +66,13 @@ pub async fn get_photo(
         return Err(AppError::AuthError);
     }
 
-    // Security check for filename to prevent path traversal
-    // Only allow alphanumeric IDs with .jpg or .png extension
-    static FILENAME_REGEX: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
-    let re = FILENAME_REGEX.get_or_init(|| regex::Regex::new(r"^[a-zA-Z0-9\-_]+\.(jpg|jpeg|png)$").unwrap());
-    
-    if !re.is_match(&filename) {
-        return Err(AppError::BadRequest("Invalid filename".to_string()));
-    }
-
-    // Ensure the filename itself doesn't have any path components
-    if std::path::Path::new(&filename).components().count() != 1 {
-        return Err(AppError::BadRequest("Invalid filename structure".to_string()));
-    }
-
-    let file_path = state.photos_dir.join(&filename);
-
-    // Canonicalize paths to ensure no symbolic link tricks or traversal
-    // However, since we strictly regex the filename, join() is safe.
-    // To be extra sure, we check that the resulting path starts with the photos directory.
-    if !file_path.starts_with(&state.photos_dir) {
-        return Err(AppError::BadRequest("Invalid path".to_string()));
-    }
+    let file_path = photo_path(&state.photos_dir, &filename)?;
 
     if !file_path.exists() {
         return Err(AppError::BadRequest("Photo not found".to_string()));
     }
 
-    let content_type = if filename.to_lowercase().ends_with(".png") {
-        "image/png"
-    } else {
-        "image/jpeg"
-    };
+    let content_type = photo_content_type(&filename);
 
     let bytes: Vec<u8> = tokio::fs::read(&file_path).await
         .map_err(|_| AppError::BadRequest("Failed to read photo".to_string()))?;
@@ -331,7 +307,14 @@ pub async fn disconnect_google_photos(
             && photos_dir.starts_with(&cwd) 
             && photos_dir != std::path::Path::new("/") 
         {
-            let _ = fs::remove_dir_all(photos_dir).await;
+            // Picked photos live at the top level; subfolders such as chore proofs are kept
+            if let Ok(mut entries) = fs::read_dir(&photos_dir).await {
+                while let Ok(Some(entry)) = entries.next_entry().await {
+                    if entry.file_type().await.is_ok_and(|t| t.is_file()) {
+                        let _ = fs::remove_file(entry.path()).await;
+                    }
+                }
+            }
         }
     }
 
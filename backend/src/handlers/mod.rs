@@ -7,4 +7,11 @@ pub mod backup;
 pub mod display;
 pub mod chore;
 pub mod weather;
-pub mod google_photos;
\ No newline at end of file
+pub mod google_photos;
+pub mod reward;
+pub mod routine;
+pub mod template;
+pub mod swap;
+pub mod away;
+pub mod goal;
+pub mod spending;
\ No newline at end of file
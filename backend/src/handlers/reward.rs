@@ -0,0 +1,398 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use sqlx::query_as;
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::reward::{
+        CreatePointsTransactionSchema, CreateRewardSchema, PointsTransaction, Redemption,
+        RedemptionQuery, RedemptionStatus, ReviewRedemptionSchema, Reward, UpdateRewardSchema,
+        UserPoints,
+    },
+    state::AppState,
+    utils::{
+        auth_helpers::require_admin,
+        ledger::{points_balance, post_points_entry, NewPointsEntry, MAX_REQUEST_AMOUNT},
+    },
+    middleware::auth::AuthUser,
+};
+
+pub async fn list_rewards(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<Reward>>, AppError> {
+    // Inactive rewards are only visible to admins
+    let rewards = query_as::<_, Reward>(
+        "SELECT * FROM rewards WHERE active = 1 OR $1 ORDER BY cost ASC, name ASC"
+    )
+        .bind(auth.is_admin())
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(rewards))
+}
+
+pub async fn create_reward(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateRewardSchema>,
+) -> Result<Json<Reward>, AppError> {
+    require_admin(&auth)?;
+
+    if payload.name.is_empty() || payload.name.len() > 200 {
+        return Err(AppError::InvalidInput("Name must be between 1 and 200 characters".to_string()));
+    }
+
+    if payload.description.as_ref().is_some_and(|d| d.len() > 500) {
+        return Err(AppError::InvalidInput("Description too long".to_string()));
+    }
+
+    if payload.cost <= 0 || payload.cost > MAX_REQUEST_AMOUNT {
+        return Err(AppError::InvalidInput("Cost must be positive and at most 100,000,000 points".to_string()));
+    }
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO rewards (id, name, description, cost) VALUES ($1, $2, $3, $4)"
+    )
+    .bind(id)
+    .bind(payload.name)
+    .bind(payload.description)
+    .bind(payload.cost)
+    .execute(&state.db)
+    .await?;
+
+    let reward = query_as::<_, Reward>("SELECT * FROM rewards WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(reward))
+}
+
+pub async fn update_reward(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateRewardSchema>,
+) -> Result<Json<Reward>, AppError> {
+    require_admin(&auth)?;
+
+    if payload.name.as_ref().is_some_and(|n| n.is_empty() || n.len() > 200) {
+        return Err(AppError::InvalidInput("Name must be between 1 and 200 characters".to_string()));
+    }
+
+    if payload.description.as_ref().is_some_and(|d| d.len() > 500) {
+        return Err(AppError::InvalidInput("Description too long".to_string()));
+    }
+
+    if payload.cost.is_some_and(|c| c <= 0 || c > MAX_REQUEST_AMOUNT) {
+        return Err(AppError::InvalidInput("Cost must be positive and at most 100,000,000 points".to_string()));
+    }
+
+    let result = sqlx::query(
+        r#"
+        UPDATE rewards
+        SET
+            name = COALESCE($1, name),
+            description = COALESCE($2, description),
+            cost = COALESCE($3, cost),
+            active = COALESCE($4, active)
+        WHERE id = $5
+        "#,
+    )
+    .bind(payload.name)
+    .bind(payload.description)
+    .bind(payload.cost)
+    .bind(payload.active)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Reward not found".to_string()));
+    }
+
+    let reward = query_as::<_, Reward>("SELECT * FROM rewards WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(reward))
+}
+
+pub async fn delete_reward(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM rewards WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Reward not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_points_balances(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<UserPoints>>, AppError> {
+    let balances = query_as::<_, UserPoints>(
+        r#"
+        SELECT u.id as user_id, u.name, COALESCE(
+            (SELECT balance FROM points_ledger
+             WHERE user_id = u.id
+             ORDER BY seq DESC
+             LIMIT 1), 0) as points
+        FROM users u
+        WHERE ($1 OR u.id = $2)
+          AND (u.role = 'child' OR EXISTS(SELECT 1 FROM points_ledger WHERE user_id = u.id))
+        ORDER BY u.name
+        "#
+    )
+    .bind(auth.is_admin())
+    .bind(auth.user_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(balances))
+}
+
+pub async fn get_points_ledger(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Vec<PointsTransaction>>, AppError> {
+    // Users can view their own points, admins can view anyone
+    if !auth.is_admin() && auth.user_id != user_id {
+        return Err(AppError::AuthError);
+    }
+
+    let ledger = query_as::<_, PointsTransaction>(
+        "SELECT * FROM points_ledger WHERE user_id = $1 ORDER BY seq DESC"
+    )
+        .bind(user_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(ledger))
+}
+
+pub async fn add_points_transaction(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<CreatePointsTransactionSchema>,
+) -> Result<Json<PointsTransaction>, AppError> {
+    require_admin(&auth)?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    if payload.description.len() > 500 {
+        return Err(AppError::InvalidInput("Description too long".to_string()));
+    }
+
+    if payload.amount.unsigned_abs() > MAX_REQUEST_AMOUNT as u64 {
+        return Err(AppError::InvalidInput("Amount must be at most 100,000,000 points either way".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let transaction = post_points_entry(&mut tx, NewPointsEntry {
+        user_id,
+        amount: payload.amount,
+        description: payload.description,
+        ..Default::default()
+    }).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(transaction))
+}
+
+pub async fn list_redemptions(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<RedemptionQuery>,
+    auth: AuthUser,
+) -> Result<Json<Vec<Redemption>>, AppError> {
+    // Users see their own requests, admins see everyone's
+    let redemptions = query_as::<_, Redemption>(
+        r#"
+        SELECT r.*, u.name as user_name
+        FROM reward_redemptions r
+        JOIN users u ON r.user_id = u.id
+        WHERE ($1 OR r.user_id = $2) AND ($3 IS NULL OR r.status = $3)
+        ORDER BY r.created_at DESC
+        "#
+    )
+    .bind(auth.is_admin())
+    .bind(auth.user_id)
+    .bind(query.status)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(redemptions))
+}
+
+pub async fn redeem_reward(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<Redemption>, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let reward = query_as::<_, Reward>("SELECT * FROM rewards WHERE id = $1 AND active = 1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Reward not found".to_string()))?;
+
+    // Points already promised to pending requests can't be spent twice
+    let pending: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(cost), 0) FROM reward_redemptions WHERE user_id = $1 AND status = 'pending'"
+    )
+        .bind(auth.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if points_balance(&mut tx, auth.user_id).await?.saturating_sub(pending) < reward.cost {
+        return Err(AppError::InvalidInput("Not enough points".to_string()));
+    }
+
+    let redemption_id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO reward_redemptions (id, user_id, reward_id, reward_name, cost)
+        VALUES ($1, $2, $3, $4, $5)
+        "#
+    )
+    .bind(redemption_id)
+    .bind(auth.user_id)
+    .bind(reward.id)
+    .bind(&reward.name)
+    .bind(reward.cost)
+    .execute(&mut *tx)
+    .await?;
+
+    let redemption = fetch_redemption(&mut tx, redemption_id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(redemption))
+}
+
+pub async fn approve_redemption(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReviewRedemptionSchema>,
+) -> Result<Json<Redemption>, AppError> {
+    review_redemption(&state, id, &auth, payload, RedemptionStatus::Approved).await
+}
+
+pub async fn deny_redemption(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReviewRedemptionSchema>,
+) -> Result<Json<Redemption>, AppError> {
+    review_redemption(&state, id, &auth, payload, RedemptionStatus::Denied).await
+}
+
+async fn review_redemption(
+    state: &AppState,
+    id: Uuid,
+    auth: &AuthUser,
+    payload: ReviewRedemptionSchema,
+    status: RedemptionStatus,
+) -> Result<Json<Redemption>, AppError> {
+    require_admin(auth)?;
+
+    if payload.note.as_ref().is_some_and(|n| n.len() > 500) {
+        return Err(AppError::InvalidInput("Note too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let redemption = fetch_redemption(&mut tx, id).await?;
+
+    if redemption.status != RedemptionStatus::Pending {
+        return Err(AppError::InvalidInput("Redemption is not pending".to_string()));
+    }
+
+    let mut points_entry_id = None;
+
+    if status == RedemptionStatus::Approved {
+        if points_balance(&mut tx, redemption.user_id).await? < redemption.cost {
+            return Err(AppError::InvalidInput("Not enough points".to_string()));
+        }
+
+        let entry = post_points_entry(&mut tx, NewPointsEntry {
+            user_id: redemption.user_id,
+            amount: -redemption.cost,
+            description: format!("Redeemed: {} (redemption {})", redemption.reward_name, redemption.id),
+            ..Default::default()
+        }).await?;
+        points_entry_id = Some(entry.id);
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE reward_redemptions
+        SET status = $1, note = $2, reviewed_by = $3, reviewed_at = datetime('now'), points_entry_id = $4
+        WHERE id = $5
+        "#
+    )
+    .bind(status)
+    .bind(payload.note)
+    .bind(auth.user_id)
+    .bind(points_entry_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let redemption = fetch_redemption(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(redemption))
+}
+
+async fn fetch_redemption(
+    conn: &mut sqlx::SqliteConnection,
+    id: Uuid,
+) -> Result<Redemption, AppError> {
+    query_as::<_, Redemption>(
+        r#"
+        SELECT r.*, u.name as user_name
+        FROM reward_redemptions r
+        JOIN users u ON r.user_id = u.id
+        WHERE r.id = $1
+        "#
+    )
+    .bind(id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(AppError::InvalidInput("Redemption not found".to_string()))
+}
@@ -0,0 +1,489 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use chrono::{Local, NaiveDate, NaiveTime};
+use sqlx::{query_as, SqliteConnection};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        routine::{
+            CreateRoutineSchema, DisplayCheckStepSchema, Routine, RoutineProgress, RoutineStepStatus,
+            UpdateRoutineSchema,
+        },
+        user::AllowanceTransaction,
+    },
+    state::AppState,
+    utils::{
+        auth_helpers::{require_admin, verify_display_pin},
+        ledger::{post_entry, NewLedgerEntry, MAX_REQUEST_AMOUNT},
+    },
+    middleware::auth::{AuthUser, DisplayAuth},
+};
+
+const MAX_STEPS: usize = 30;
+
+fn validate_routine(
+    name: Option<&str>,
+    window_start: Option<NaiveTime>,
+    window_end: Option<NaiveTime>,
+    reward: Option<i64>,
+    steps: Option<&[String]>,
+) -> Result<(), AppError> {
+    if name.is_some_and(|n| n.is_empty() || n.len() > 200) {
+        return Err(AppError::InvalidInput("Name must be between 1 and 200 characters".to_string()));
+    }
+
+    if let (Some(start), Some(end)) = (window_start, window_end)
+        && start >= end
+    {
+        return Err(AppError::InvalidInput("Window must start before it ends".to_string()));
+    }
+
+    if reward.is_some_and(|r| !(0..=MAX_REQUEST_AMOUNT).contains(&r)) {
+        return Err(AppError::InvalidInput("Reward must be between 0 and 1,000,000.00".to_string()));
+    }
+
+    if let Some(steps) = steps {
+        if steps.is_empty() || steps.len() > MAX_STEPS {
+            return Err(AppError::InvalidInput(format!("Routines need between 1 and {} steps", MAX_STEPS)));
+        }
+        if steps.iter().any(|s| s.is_empty() || s.len() > 200) {
+            return Err(AppError::InvalidInput("Step must be between 1 and 200 characters".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+async fn replace_steps(conn: &mut SqliteConnection, routine_id: Uuid, steps: &[String]) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM routine_steps WHERE routine_id = $1")
+        .bind(routine_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (position, description) in steps.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO routine_steps (id, routine_id, position, description) VALUES ($1, $2, $3, $4)"
+        )
+        .bind(Uuid::new_v4())
+        .bind(routine_id)
+        .bind(position as i64)
+        .bind(description)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Build each routine's checklist state for the given day
+pub async fn routine_progress(
+    conn: &mut SqliteConnection,
+    routines: Vec<Routine>,
+    date: NaiveDate,
+    now: NaiveTime,
+) -> Result<Vec<RoutineProgress>, AppError> {
+    let mut progress = Vec::with_capacity(routines.len());
+
+    for routine in routines {
+        let steps = query_as::<_, RoutineStepStatus>(
+            r#"
+            SELECT s.id, s.position, s.description,
+                   c.step_id IS NOT NULL as checked, c.checked_at
+            FROM routine_steps s
+            LEFT JOIN routine_step_checks c ON c.step_id = s.id AND c.check_date = $2
+            WHERE s.routine_id = $1
+            ORDER BY s.position ASC
+            "#
+        )
+        .bind(routine.id)
+        .bind(date)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let user_name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+            .bind(routine.user_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let completed_steps = steps.iter().filter(|s| s.checked).count();
+
+        progress.push(RoutineProgress {
+            in_window: routine.in_window(now),
+            routine,
+            user_name,
+            date,
+            completed: !steps.is_empty() && completed_steps == steps.len(),
+            completed_steps,
+            steps,
+        });
+    }
+
+    Ok(progress)
+}
+
+async fn fetch_progress(conn: &mut SqliteConnection, id: Uuid) -> Result<RoutineProgress, AppError> {
+    let routine = query_as::<_, Routine>("SELECT * FROM routines WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::InvalidInput("Routine not found".to_string()))?;
+
+    let now = Local::now().naive_local();
+    let mut progress = routine_progress(&mut *conn, vec![routine], now.date(), now.time()).await?;
+
+    Ok(progress.remove(0))
+}
+
+/// Check or uncheck a step for today, paying or reversing the routine's reward
+/// when the checklist becomes complete or incomplete.
+///
+/// With `check_only` a step that is already checked is an error rather than unchecked.
+async fn toggle_step(
+    conn: &mut SqliteConnection,
+    routine: &Routine,
+    step_id: Uuid,
+    checked_by: Option<Uuid>,
+    check_only: bool,
+) -> Result<(), AppError> {
+    let today = Local::now().date_naive();
+
+    let step_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM routine_steps WHERE id = $1 AND routine_id = $2)"
+    )
+        .bind(step_id)
+        .bind(routine.id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if !step_exists {
+        return Err(AppError::InvalidInput("Step not found".to_string()));
+    }
+
+    if check_only {
+        let checked: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM routine_step_checks WHERE step_id = $1 AND check_date = $2)"
+        )
+            .bind(step_id)
+            .bind(today)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        if checked {
+            return Err(AppError::InvalidInput("Step is already checked".to_string()));
+        }
+    }
+
+    let removed = sqlx::query("DELETE FROM routine_step_checks WHERE step_id = $1 AND check_date = $2")
+        .bind(step_id)
+        .bind(today)
+        .execute(&mut *conn)
+        .await?;
+
+    if removed.rows_affected() == 0 {
+        sqlx::query("INSERT INTO routine_step_checks (step_id, check_date, checked_by) VALUES ($1, $2, $3)")
+            .bind(step_id)
+            .bind(today)
+            .bind(checked_by)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    let (total, checked): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COUNT(c.step_id)
+        FROM routine_steps s
+        LEFT JOIN routine_step_checks c ON c.step_id = s.id AND c.check_date = $2
+        WHERE s.routine_id = $1
+        "#
+    )
+    .bind(routine.id)
+    .bind(today)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let completion: Option<Option<Uuid>> = sqlx::query_scalar(
+        "SELECT reward_entry_id FROM routine_completions WHERE routine_id = $1 AND completion_date = $2"
+    )
+        .bind(routine.id)
+        .bind(today)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    match completion {
+        None if total > 0 && checked == total => {
+            let mut reward_entry_id = None;
+            if let Some(reward) = routine.reward.filter(|r| *r > 0) {
+                let entry = post_entry(&mut *conn, NewLedgerEntry {
+                    user_id: routine.user_id,
+                    amount: reward,
+                    description: format!("Routine reward: {} (routine {})", routine.name, routine.id),
+                    ..Default::default()
+                }).await?;
+                reward_entry_id = Some(entry.id);
+            }
+
+            sqlx::query(
+                "INSERT INTO routine_completions (routine_id, completion_date, reward_entry_id) VALUES ($1, $2, $3)"
+            )
+            .bind(routine.id)
+            .bind(today)
+            .bind(reward_entry_id)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Some(reward_entry_id) if checked < total => {
+            if let Some(entry_id) = reward_entry_id {
+                let paid = query_as::<_, AllowanceTransaction>("SELECT * FROM allowance_ledger WHERE id = $1 AND voided_at IS NULL")
+                    .bind(entry_id)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+
+                if let Some(paid) = paid {
+                    post_entry(&mut *conn, NewLedgerEntry {
+                        user_id: paid.user_id,
+                        amount: -paid.amount,
+                        description: format!("Routine reward reversed: {} (routine {})", routine.name, routine.id),
+                        reverses: Some(paid.id),
+                        ..Default::default()
+                    }).await?;
+                }
+            }
+
+            sqlx::query("DELETE FROM routine_completions WHERE routine_id = $1 AND completion_date = $2")
+                .bind(routine.id)
+                .bind(today)
+                .execute(&mut *conn)
+                .await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub async fn list_routines(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> Result<Json<Vec<RoutineProgress>>, AppError> {
+    // Users see their own routines, admins see everyone's
+    let routines = query_as::<_, Routine>(
+        "SELECT * FROM routines WHERE $1 OR user_id = $2 ORDER BY window_start ASC, name ASC"
+    )
+        .bind(auth.is_admin())
+        .bind(auth.user_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    let now = Local::now().naive_local();
+    let mut conn = state.db.acquire().await?;
+    let progress = routine_progress(&mut conn, routines, now.date(), now.time()).await?;
+
+    Ok(Json(progress))
+}
+
+pub async fn create_routine(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateRoutineSchema>,
+) -> Result<Json<RoutineProgress>, AppError> {
+    require_admin(&auth)?;
+
+    validate_routine(
+        Some(&payload.name),
+        payload.window_start,
+        payload.window_end,
+        payload.reward,
+        Some(&payload.steps),
+    )?;
+
+    let user_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)"
+    )
+        .bind(payload.user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    if !user_exists {
+        return Err(AppError::UserNotFound);
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO routines (id, user_id, name, window_start, window_end, reward)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#
+    )
+    .bind(id)
+    .bind(payload.user_id)
+    .bind(&payload.name)
+    .bind(payload.window_start)
+    .bind(payload.window_end)
+    .bind(payload.reward)
+    .execute(&mut *tx)
+    .await?;
+
+    replace_steps(&mut tx, id, &payload.steps).await?;
+
+    let progress = fetch_progress(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(progress))
+}
+
+pub async fn update_routine(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateRoutineSchema>,
+) -> Result<Json<RoutineProgress>, AppError> {
+    require_admin(&auth)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let routine = query_as::<_, Routine>("SELECT * FROM routines WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Routine not found".to_string()))?;
+
+    validate_routine(
+        payload.name.as_deref(),
+        payload.window_start.or(routine.window_start),
+        payload.window_end.or(routine.window_end),
+        payload.reward,
+        payload.steps.as_deref(),
+    )?;
+
+    sqlx::query(
+        r#"
+        UPDATE routines
+        SET
+            name = COALESCE($1, name),
+            window_start = COALESCE($2, window_start),
+            window_end = COALESCE($3, window_end),
+            reward = COALESCE($4, reward),
+            active = COALESCE($5, active)
+        WHERE id = $6
+        "#
+    )
+    .bind(payload.name)
+    .bind(payload.window_start)
+    .bind(payload.window_end)
+    .bind(payload.reward)
+    .bind(payload.active)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(steps) = payload.steps {
+        replace_steps(&mut tx, id, &steps).await?;
+    }
+
+    let progress = fetch_progress(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(progress))
+}
+
+pub async fn delete_routine(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM routines WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Routine not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn toggle_routine_step(
+    State(state): State<Arc<AppState>>,
+    Path((id, step_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+) -> Result<Json<RoutineProgress>, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let routine = query_as::<_, Routine>("SELECT * FROM routines WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Routine not found".to_string()))?;
+
+    // Only admin or the routine's owner can check steps
+    if !auth.is_admin() && routine.user_id != auth.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    if !routine.active {
+        return Err(AppError::InvalidInput("Routine is not active".to_string()));
+    }
+
+    toggle_step(&mut tx, &routine, step_id, Some(auth.user_id), false).await?;
+
+    let progress = fetch_progress(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(progress))
+}
+
+/// Check off a step on a display, with the routine owner's PIN.
+///
+/// Displays can only check steps, never uncheck them, since a routine's
+/// completion pays into the allowance ledger.
+pub async fn display_check_routine_step(
+    State(state): State<Arc<AppState>>,
+    Path((id, step_id)): Path<(Uuid, Uuid)>,
+    kiosk: DisplayAuth,
+    Json(payload): Json<DisplayCheckStepSchema>,
+) -> Result<Json<RoutineProgress>, AppError> {
+    let owner: Option<Uuid> = sqlx::query_scalar("SELECT user_id FROM routines WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let owner = owner.ok_or(AppError::InvalidInput("Routine not found".to_string()))?;
+
+    verify_display_pin(&state, kiosk.token_id, owner, &payload.pin).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let routine = query_as::<_, Routine>("SELECT * FROM routines WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Routine not found".to_string()))?;
+
+    // Displays may only check routines that are currently showing
+    if !routine.active || !routine.in_window(Local::now().time()) {
+        return Err(AppError::InvalidInput("Routine is not active".to_string()));
+    }
+
+    toggle_step(&mut tx, &routine, step_id, None, true).await?;
+    tracing::debug!(display_id = %kiosk.token_id, routine_id = %id, "Routine step checked from display");
+
+    let progress = fetch_progress(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(progress))
+}
@@ -33,6 +33,12 @@ pub async fn get_settings(
             "openweather_api_key" => settings.openweather_api_key = row.value,
             "google_client_id" => settings.google_client_id = row.value,
             "google_client_secret" => settings.google_client_secret = row.value,
+            "chore_proof_retention_days" => settings.chore_proof_retention_days = row.value,
+            "chore_swaps_need_approval" => settings.chore_swaps_need_approval = row.value == "true",
+            "currency_code" => settings.currency_code = row.value,
+            "currency_decimals" => settings.currency_decimals = row.value,
+            "overdraft_policy" => settings.overdraft_policy = row.value,
+            "overdraft_limit" => settings.overdraft_limit = row.value,
             "google_photos_access_token" => settings.google_photos_access_token = row.value,
             "google_photos_refresh_token" => {
                 if !row.value.is_empty() {
@@ -180,5 +186,87 @@ pub async fn update_settings(
         .await?;
     }
 
+    if let Some(days) = payload.chore_proof_retention_days {
+        if !days.is_empty() && !days.parse::<i64>().is_ok_and(|d| (1..=3650).contains(&d)) {
+            return Err(AppError::InvalidInput("Retention must be between 1 and 3650 days".to_string()));
+        }
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ($1, $2) 
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = datetime('now')"
+        )
+        .bind("chore_proof_retention_days")
+        .bind(days)
+        .execute(&state.db)
+        .await?;
+    }
+
+    if let Some(need_approval) = payload.chore_swaps_need_approval {
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ($1, $2) 
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = datetime('now')"
+        )
+        .bind("chore_swaps_need_approval")
+        .bind(need_approval.to_string())
+        .execute(&state.db)
+        .await?;
+    }
+
+    if let Some(code) = payload.currency_code {
+        let code = code.trim().to_uppercase();
+        if !code.is_empty() && (code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase())) {
+            return Err(AppError::InvalidInput("Currency must be a 3 letter code like USD".to_string()));
+        }
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ($1, $2) 
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = datetime('now')"
+        )
+        .bind("currency_code")
+        .bind(code)
+        .execute(&state.db)
+        .await?;
+    }
+
+    if let Some(decimals) = payload.currency_decimals {
+        if !decimals.is_empty() && !decimals.parse::<u32>().is_ok_and(|d| d <= 3) {
+            return Err(AppError::InvalidInput("Currency decimals must be between 0 and 3".to_string()));
+        }
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ($1, $2) 
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = datetime('now')"
+        )
+        .bind("currency_decimals")
+        .bind(decimals)
+        .execute(&state.db)
+        .await?;
+    }
+
+    if let Some(policy) = payload.overdraft_policy {
+        if !matches!(policy.as_str(), "" | "block" | "limit" | "allow") {
+            return Err(AppError::InvalidInput("Overdraft policy must be block, limit or allow".to_string()));
+        }
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ($1, $2) 
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = datetime('now')"
+        )
+        .bind("overdraft_policy")
+        .bind(policy)
+        .execute(&state.db)
+        .await?;
+    }
+
+    if let Some(limit) = payload.overdraft_limit {
+        if !limit.is_empty() && !limit.parse::<i64>().is_ok_and(|l| l >= 0) {
+            return Err(AppError::InvalidInput("Overdraft limit must be a positive number of cents".to_string()));
+        }
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ($1, $2) 
+             ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = datetime('now')"
+        )
+        .bind("overdraft_limit")
+        .bind(limit)
+        .execute(&state.db)
+        .await?;
+    }
+
     get_settings(State(state), auth).await
 }
\ No newline at end of file
@@ -0,0 +1,224 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use std::sync::Arc;
+use sqlx::{query_as, SqliteConnection};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::spending::{
+        CreateSpendingSchema, ReviewSpendingSchema, SpendingQuery, SpendingRequest, SpendingStatus,
+    },
+    state::AppState,
+    utils::{
+        auth_helpers::require_admin,
+        ledger::{check_overdraft, post_entry, user_buckets, NewLedgerEntry, MAX_REQUEST_AMOUNT},
+    },
+    middleware::auth::AuthUser,
+};
+
+pub const SPENDING_SELECT: &str = r#"
+    SELECT s.*, u.name as user_name, b.name as bucket_name
+    FROM spending_requests s
+    JOIN users u ON s.user_id = u.id
+    LEFT JOIN allowance_buckets b ON s.bucket_id = b.id
+"#;
+
+async fn fetch_request(conn: &mut SqliteConnection, id: Uuid) -> Result<SpendingRequest, AppError> {
+    query_as::<_, SpendingRequest>(&format!("{} WHERE s.id = $1", SPENDING_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::InvalidInput("Spending request not found".to_string()))
+}
+
+pub async fn list_requests(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SpendingQuery>,
+    auth: AuthUser,
+) -> Result<Json<Vec<SpendingRequest>>, AppError> {
+    // Users see their own requests, admins see everyone's
+    let requests = query_as::<_, SpendingRequest>(&format!(
+        r#"{}
+        WHERE ($1 OR s.user_id = $2) AND ($3 IS NULL OR s.status = $3)
+        ORDER BY s.created_at DESC
+        "#,
+        SPENDING_SELECT
+    ))
+    .bind(auth.is_admin())
+    .bind(auth.user_id)
+    .bind(query.status)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(requests))
+}
+
+pub async fn create_request(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateSpendingSchema>,
+) -> Result<Json<SpendingRequest>, AppError> {
+    if payload.amount <= 0 || payload.amount > MAX_REQUEST_AMOUNT {
+        return Err(AppError::InvalidInput("Amount must be positive and at most 1,000,000.00".to_string()));
+    }
+
+    if payload.description.trim().is_empty() || payload.description.len() > 500 {
+        return Err(AppError::InvalidInput("Description must be between 1 and 500 characters".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    if let Some(bucket_id) = payload.bucket_id
+        && !user_buckets(&mut tx, auth.user_id).await?.iter().any(|b| b.id == bucket_id)
+    {
+        return Err(AppError::InvalidInput("Bucket not found".to_string()));
+    }
+
+    // Money already promised to pending requests can't be asked for twice
+    let pending: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COALESCE(SUM(amount), 0) FROM spending_requests
+        WHERE user_id = $1 AND status = 'pending' AND bucket_id IS $2
+        "#
+    )
+        .bind(auth.user_id)
+        .bind(payload.bucket_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let requested = pending
+        .checked_add(payload.amount)
+        .ok_or(AppError::InvalidInput("Not enough money".to_string()))?;
+    check_overdraft(&mut tx, auth.user_id, payload.bucket_id, requested).await?;
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO spending_requests (id, user_id, bucket_id, amount, description)
+        VALUES ($1, $2, $3, $4, $5)
+        "#
+    )
+    .bind(id)
+    .bind(auth.user_id)
+    .bind(payload.bucket_id)
+    .bind(payload.amount)
+    .bind(payload.description.trim())
+    .execute(&mut *tx)
+    .await?;
+
+    let request = fetch_request(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(request))
+}
+
+pub async fn cancel_request(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<SpendingRequest>, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let request = fetch_request(&mut tx, id).await?;
+
+    if request.user_id != auth.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    if request.status != SpendingStatus::Pending {
+        return Err(AppError::InvalidInput("Spending request is not pending".to_string()));
+    }
+
+    sqlx::query("UPDATE spending_requests SET status = $1 WHERE id = $2")
+        .bind(SpendingStatus::Cancelled)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    let request = fetch_request(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(request))
+}
+
+pub async fn approve_request(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReviewSpendingSchema>,
+) -> Result<Json<SpendingRequest>, AppError> {
+    review_request(&state, id, &auth, payload, SpendingStatus::Approved).await
+}
+
+pub async fn deny_request(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ReviewSpendingSchema>,
+) -> Result<Json<SpendingRequest>, AppError> {
+    review_request(&state, id, &auth, payload, SpendingStatus::Denied).await
+}
+
+async fn review_request(
+    state: &AppState,
+    id: Uuid,
+    auth: &AuthUser,
+    payload: ReviewSpendingSchema,
+    status: SpendingStatus,
+) -> Result<Json<SpendingRequest>, AppError> {
+    require_admin(auth)?;
+
+    if payload.note.as_ref().is_some_and(|n| n.len() > 500) {
+        return Err(AppError::InvalidInput("Note too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let request = fetch_request(&mut tx, id).await?;
+
+    if request.status != SpendingStatus::Pending {
+        return Err(AppError::InvalidInput("Spending request is not pending".to_string()));
+    }
+
+    let mut entry_id = None;
+
+    if status == SpendingStatus::Approved {
+        // Checked again here since the balance may have changed since the request
+        check_overdraft(&mut tx, request.user_id, request.bucket_id, request.amount).await?;
+
+        let entry = post_entry(&mut tx, NewLedgerEntry {
+            user_id: request.user_id,
+            amount: -request.amount,
+            description: format!("Spent: {} (request {})", request.description, request.id),
+            bucket_id: request.bucket_id,
+            ..Default::default()
+        }).await?;
+        entry_id = Some(entry.id);
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE spending_requests
+        SET status = $1, note = $2, reviewed_by = $3, reviewed_at = datetime('now'), entry_id = $4
+        WHERE id = $5
+        "#
+    )
+    .bind(status)
+    .bind(payload.note)
+    .bind(auth.user_id)
+    .bind(entry_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let request = fetch_request(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(request))
+}
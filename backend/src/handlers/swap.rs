@@ -0,0 +1,354 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use std::sync::Arc;
+use sqlx::{query_as, SqliteConnection};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        chore::{Chore, ChoreEventType, ReviewStatus},
+        swap::{ChoreSwap, CreateSwapSchema, SwapQuery, SwapStatus},
+    },
+    state::AppState,
+    utils::auth_helpers::require_admin,
+    middleware::auth::AuthUser,
+};
+
+const SWAP_SELECT: &str = r#"
+    SELECT s.*, pu.name as proposer_name, pc.description as proposer_chore_description,
+           ru.name as recipient_name, rc.description as recipient_chore_description
+    FROM chore_swaps s
+    LEFT JOIN users pu ON s.proposer_id = pu.id
+    LEFT JOIN chores pc ON s.proposer_chore_id = pc.id
+    LEFT JOIN users ru ON s.recipient_id = ru.id
+    LEFT JOIN chores rc ON s.recipient_chore_id = rc.id
+"#;
+
+async fn fetch_swap(conn: &mut SqliteConnection, id: Uuid) -> Result<ChoreSwap, AppError> {
+    query_as::<_, ChoreSwap>(&format!("{} WHERE s.id = $1", SWAP_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::InvalidInput("Swap not found".to_string()))
+}
+
+/// Check a chore can still change hands
+fn ensure_swappable(chore: &Chore) -> Result<(), AppError> {
+    if chore.completed || chore.review_status == Some(ReviewStatus::Pending) {
+        return Err(AppError::InvalidInput("Finished chores can't be swapped".to_string()));
+    }
+
+    if chore.rotation_period_days.is_some() {
+        return Err(AppError::InvalidInput("Rotating chores can't be swapped".to_string()));
+    }
+
+    Ok(())
+}
+
+pub async fn list_swaps(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<SwapQuery>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChoreSwap>>, AppError> {
+    // Users see swaps they're part of, admins see everyone's
+    let swaps = query_as::<_, ChoreSwap>(&format!(
+        r#"{}
+        WHERE ($1 OR s.proposer_id = $2 OR s.recipient_id = $2) AND ($3 IS NULL OR s.status = $3)
+        ORDER BY s.created_at DESC
+        "#,
+        SWAP_SELECT
+    ))
+    .bind(auth.is_admin())
+    .bind(auth.user_id)
+    .bind(query.status)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(swaps))
+}
+
+pub async fn create_swap(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateSwapSchema>,
+) -> Result<Json<ChoreSwap>, AppError> {
+    if payload.note.as_ref().is_some_and(|n| n.len() > 500) {
+        return Err(AppError::InvalidInput("Note too long".to_string()));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(payload.chore_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    let other = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(payload.other_chore_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    // Users can only offer their own chores
+    if chore.assigned_to != Some(auth.user_id) {
+        return Err(AppError::AuthError);
+    }
+
+    let recipient_id = other
+        .assigned_to
+        .filter(|id| *id != auth.user_id)
+        .ok_or(AppError::InvalidInput("Chores can only be swapped with someone else".to_string()))?;
+
+    ensure_swappable(&chore)?;
+    ensure_swappable(&other)?;
+
+    let open: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM chore_swaps
+            WHERE status IN ('pending', 'accepted')
+              AND (proposer_chore_id IN ($1, $2) OR recipient_chore_id IN ($1, $2))
+        )
+        "#
+    )
+    .bind(chore.id)
+    .bind(other.id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if open {
+        return Err(AppError::InvalidInput("One of these chores already has a swap in progress".to_string()));
+    }
+
+    let needs_approval: bool = sqlx::query_scalar::<_, String>(
+        "SELECT value FROM settings WHERE key = 'chore_swaps_need_approval'"
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .is_some_and(|v| v == "true");
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO chore_swaps (id, proposer_id, proposer_chore_id, recipient_id, recipient_chore_id,
+                                 needs_approval, note)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#
+    )
+    .bind(id)
+    .bind(auth.user_id)
+    .bind(chore.id)
+    .bind(recipient_id)
+    .bind(other.id)
+    .bind(needs_approval)
+    .bind(payload.note)
+    .execute(&mut *tx)
+    .await?;
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(swap))
+}
+
+pub async fn accept_swap(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<ChoreSwap>, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    if swap.recipient_id != auth.user_id {
+        return Err(AppError::AuthError);
+    }
+
+    if swap.status != SwapStatus::Pending {
+        return Err(AppError::InvalidInput("Swap is not pending".to_string()));
+    }
+
+    if swap.needs_approval {
+        set_status(&mut tx, id, SwapStatus::Accepted, None).await?;
+    } else {
+        exchange_chores(&mut tx, &swap, auth.user_id).await?;
+        set_status(&mut tx, id, SwapStatus::Completed, None).await?;
+    }
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(swap))
+}
+
+pub async fn decline_swap(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<ChoreSwap>, AppError> {
+    close_swap(&state, id, &auth, SwapStatus::Declined).await
+}
+
+pub async fn cancel_swap(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<ChoreSwap>, AppError> {
+    close_swap(&state, id, &auth, SwapStatus::Cancelled).await
+}
+
+pub async fn confirm_swap(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<ChoreSwap>, AppError> {
+    require_admin(&auth)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    if swap.status != SwapStatus::Accepted {
+        return Err(AppError::InvalidInput("Swap is not waiting for confirmation".to_string()));
+    }
+
+    exchange_chores(&mut tx, &swap, auth.user_id).await?;
+    set_status(&mut tx, id, SwapStatus::Completed, Some(auth.user_id)).await?;
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(swap))
+}
+
+pub async fn reject_swap(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<Json<ChoreSwap>, AppError> {
+    require_admin(&auth)?;
+
+    close_swap(&state, id, &auth, SwapStatus::Rejected).await
+}
+
+/// End a swap without exchanging anything
+async fn close_swap(
+    state: &AppState,
+    id: Uuid,
+    auth: &AuthUser,
+    status: SwapStatus,
+) -> Result<Json<ChoreSwap>, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    // The recipient declines, the proposer cancels, a parent rejects
+    let (allowed, open) = match status {
+        SwapStatus::Declined => (swap.recipient_id == auth.user_id, swap.status == SwapStatus::Pending),
+        SwapStatus::Cancelled => (
+            swap.proposer_id == auth.user_id,
+            matches!(swap.status, SwapStatus::Pending | SwapStatus::Accepted),
+        ),
+        _ => (auth.is_admin(), matches!(swap.status, SwapStatus::Pending | SwapStatus::Accepted)),
+    };
+
+    if !allowed {
+        return Err(AppError::AuthError);
+    }
+
+    if !open {
+        return Err(AppError::InvalidInput("Swap is already closed".to_string()));
+    }
+
+    let reviewer = (status == SwapStatus::Rejected).then_some(auth.user_id);
+    set_status(&mut tx, id, status, reviewer).await?;
+
+    let swap = fetch_swap(&mut tx, id).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(swap))
+}
+
+async fn set_status(
+    conn: &mut SqliteConnection,
+    id: Uuid,
+    status: SwapStatus,
+    reviewer: Option<Uuid>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE chore_swaps
+        SET status = $1,
+            responded_at = CASE WHEN status = 'pending' AND $1 != 'cancelled' THEN datetime('now') ELSE responded_at END,
+            reviewed_by = COALESCE($2, reviewed_by),
+            reviewed_at = CASE WHEN $2 IS NOT NULL THEN datetime('now') ELSE reviewed_at END
+        WHERE id = $3
+        "#
+    )
+    .bind(status)
+    .bind(reviewer)
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Hand each chore to the other person and record the trade in both chores' history.
+///
+/// Fails if either chore changed hands or was finished since the swap was proposed.
+async fn exchange_chores(
+    conn: &mut SqliteConnection,
+    swap: &ChoreSwap,
+    actor_id: Uuid,
+) -> Result<(), AppError> {
+    let moves = [
+        (swap.proposer_chore_id, swap.proposer_id, swap.recipient_id),
+        (swap.recipient_chore_id, swap.recipient_id, swap.proposer_id),
+    ];
+
+    for (chore_id, from, to) in moves {
+        let result = sqlx::query(
+            r#"
+            UPDATE chores SET assigned_to = $1, updated_at = datetime('now')
+            WHERE id = $2 AND assigned_to = $3 AND completed = 0
+              AND (review_status IS NULL OR review_status != 'pending')
+            "#
+        )
+        .bind(to)
+        .bind(chore_id)
+        .bind(from)
+        .execute(&mut *conn)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::InvalidInput("Chores have changed since the swap was proposed".to_string()));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO chore_events (id, chore_id, chore_ref, user_id, actor_id, event_type)
+            VALUES ($1, $2, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(chore_id)
+        .bind(to)
+        .bind(actor_id)
+        .bind(ChoreEventType::Swapped)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    tracing::info!(swap_id = %swap.id, "Chores swapped");
+
+    Ok(())
+}
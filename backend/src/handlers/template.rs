@@ -0,0 +1,394 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use chrono::Local;
+use sqlx::query_as;
+use uuid::Uuid;
+
+use crate::{
+    background,
+    error::AppError,
+    handlers::chore::{validate_points, validate_recurrence, validate_reward, validate_window},
+    models::{
+        chore::Chore,
+        template::{
+            age_on, AssignTemplateSchema, ChoreTemplate, CreateTemplateSchema, ListTemplatesQuery,
+            SaveTemplateSchema, UpdateTemplateSchema,
+        },
+        user::User,
+    },
+    state::AppState,
+    utils::auth_helpers::require_admin,
+    middleware::auth::AuthUser,
+};
+
+fn validate_template(template: &ChoreTemplate) -> Result<(), AppError> {
+    if template.description.is_empty() || template.description.len() > 500 {
+        return Err(AppError::InvalidInput("Description must be between 1 and 500 characters".to_string()));
+    }
+
+    validate_recurrence(
+        template.recurrence,
+        template.recurrence_weekdays.as_deref(),
+        template.recurrence_interval,
+        template.recurrence_day,
+    )?;
+
+    validate_window(template.window_start, template.window_end, template.window_label.as_deref())?;
+
+    validate_reward(template.reward)?;
+
+    validate_points(template.points)?;
+
+    if template.min_age.is_some_and(|a| !(0..=120).contains(&a))
+        || template.max_age.is_some_and(|a| !(0..=120).contains(&a))
+    {
+        return Err(AppError::InvalidInput("Ages must be between 0 and 120".to_string()));
+    }
+
+    if let (Some(min), Some(max)) = (template.min_age, template.max_age)
+        && min > max
+    {
+        return Err(AppError::InvalidInput("Minimum age must not be above maximum age".to_string()));
+    }
+
+    Ok(())
+}
+
+async fn insert_template(
+    conn: &mut sqlx::SqliteConnection,
+    template: &ChoreTemplate,
+) -> Result<ChoreTemplate, AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO chore_templates (id, description, reward, points, recurrence, recurrence_weekdays,
+                                     recurrence_interval, recurrence_day, window_start, window_end,
+                                     window_label, min_age, max_age)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        "#,
+    )
+    .bind(template.id)
+    .bind(&template.description)
+    .bind(template.reward)
+    .bind(template.points)
+    .bind(template.recurrence)
+    .bind(&template.recurrence_weekdays)
+    .bind(template.recurrence_interval)
+    .bind(template.recurrence_day)
+    .bind(template.window_start)
+    .bind(template.window_end)
+    .bind(&template.window_label)
+    .bind(template.min_age)
+    .bind(template.max_age)
+    .execute(&mut *conn)
+    .await?;
+
+    let template = query_as::<_, ChoreTemplate>("SELECT * FROM chore_templates WHERE id = $1")
+        .bind(template.id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(template)
+}
+
+pub async fn list_templates(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListTemplatesQuery>,
+    auth: AuthUser,
+) -> Result<Json<Vec<ChoreTemplate>>, AppError> {
+    require_admin(&auth)?;
+
+    let mut templates = query_as::<_, ChoreTemplate>(
+        "SELECT * FROM chore_templates ORDER BY description ASC"
+    )
+        .fetch_all(&state.db)
+        .await?;
+
+    // Narrow the list down to what suits a particular person
+    if let Some(user_id) = query.user_id {
+        let user = query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        if let Some(birthday) = user.birthday {
+            let age = age_on(birthday, Local::now().date_naive());
+            templates.retain(|t| t.suits_age(age));
+        }
+    }
+
+    Ok(Json(templates))
+}
+
+pub async fn create_template(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Json(payload): Json<CreateTemplateSchema>,
+) -> Result<Json<ChoreTemplate>, AppError> {
+    require_admin(&auth)?;
+
+    let now = chrono::Utc::now();
+    let template = ChoreTemplate {
+        id: Uuid::new_v4(),
+        description: payload.description,
+        reward: payload.reward,
+        points: payload.points,
+        recurrence: payload.recurrence,
+        recurrence_weekdays: payload.recurrence_weekdays,
+        recurrence_interval: payload.recurrence_interval,
+        recurrence_day: payload.recurrence_day,
+        window_start: payload.window_start,
+        window_end: payload.window_end,
+        window_label: payload.window_label,
+        min_age: payload.min_age,
+        max_age: payload.max_age,
+        created_at: now,
+        updated_at: now,
+    };
+
+    validate_template(&template)?;
+
+    let mut conn = state.db.acquire().await?;
+    let template = insert_template(&mut conn, &template).await?;
+
+    Ok(Json(template))
+}
+
+pub async fn update_template(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<UpdateTemplateSchema>,
+) -> Result<Json<ChoreTemplate>, AppError> {
+    require_admin(&auth)?;
+
+    let existing = query_as::<_, ChoreTemplate>("SELECT * FROM chore_templates WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Template not found".to_string()))?;
+
+    // Validate the template as it will look after the update
+    let merged = ChoreTemplate {
+        description: payload.description.clone().unwrap_or(existing.description),
+        reward: payload.reward.or(existing.reward),
+        points: payload.points.or(existing.points),
+        recurrence: payload.recurrence.or(existing.recurrence),
+        recurrence_weekdays: payload.recurrence_weekdays.clone().or(existing.recurrence_weekdays),
+        recurrence_interval: payload.recurrence_interval.or(existing.recurrence_interval),
+        recurrence_day: payload.recurrence_day.or(existing.recurrence_day),
+        window_start: payload.window_start.or(existing.window_start),
+        window_end: payload.window_end.or(existing.window_end),
+        window_label: payload.window_label.clone().or(existing.window_label),
+        min_age: payload.min_age.or(existing.min_age),
+        max_age: payload.max_age.or(existing.max_age),
+        ..existing
+    };
+
+    validate_template(&merged)?;
+
+    sqlx::query(
+        r#"
+        UPDATE chore_templates
+        SET
+            description = COALESCE($1, description),
+            reward = COALESCE($2, reward),
+            points = COALESCE($3, points),
+            recurrence = COALESCE($4, recurrence),
+            recurrence_weekdays = COALESCE($5, recurrence_weekdays),
+            recurrence_interval = COALESCE($6, recurrence_interval),
+            recurrence_day = COALESCE($7, recurrence_day),
+            window_start = COALESCE($8, window_start),
+            window_end = COALESCE($9, window_end),
+            window_label = COALESCE($10, window_label),
+            min_age = COALESCE($11, min_age),
+            max_age = COALESCE($12, max_age)
+        WHERE id = $13
+        "#,
+    )
+    .bind(payload.description)
+    .bind(payload.reward)
+    .bind(payload.points)
+    .bind(payload.recurrence)
+    .bind(payload.recurrence_weekdays)
+    .bind(payload.recurrence_interval)
+    .bind(payload.recurrence_day)
+    .bind(payload.window_start)
+    .bind(payload.window_end)
+    .bind(payload.window_label)
+    .bind(payload.min_age)
+    .bind(payload.max_age)
+    .bind(id)
+    .execute(&state.db)
+    .await?;
+
+    let template = query_as::<_, ChoreTemplate>("SELECT * FROM chore_templates WHERE id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(template))
+}
+
+pub async fn delete_template(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+) -> Result<StatusCode, AppError> {
+    require_admin(&auth)?;
+
+    let result = sqlx::query("DELETE FROM chore_templates WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::InvalidInput("Template not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn assign_template(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<AssignTemplateSchema>,
+) -> Result<Json<Vec<Chore>>, AppError> {
+    require_admin(&auth)?;
+
+    if payload.user_ids.is_empty() {
+        return Err(AppError::InvalidInput("Choose at least one person".to_string()));
+    }
+
+    let template = query_as::<_, ChoreTemplate>("SELECT * FROM chore_templates WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Template not found".to_string()))?;
+
+    if template.recurrence.is_some() && payload.due_date.is_some() {
+        return Err(AppError::InvalidInput("Recurring chores are due on each occurrence date".to_string()));
+    }
+
+    let today = Local::now().date_naive();
+    let recurrence_start = template
+        .recurrence
+        .map(|_| payload.recurrence_start.unwrap_or(today));
+
+    // Either every chore is created or none are
+    let mut tx = state.db.begin().await?;
+    let mut chores = Vec::with_capacity(payload.user_ids.len());
+
+    for user_id in payload.user_ids {
+        let user = query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(AppError::InvalidInput("Assigned user not found".to_string()))?;
+
+        if let Some(birthday) = user.birthday
+            && !template.suits_age(age_on(birthday, today))
+        {
+            return Err(AppError::InvalidInput(format!("{} is outside the template's age range", user.name)));
+        }
+
+        let chore_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO chores (id, description, assigned_to, reward, recurrence, recurrence_weekdays,
+                                recurrence_interval, recurrence_day, recurrence_start, due_date,
+                                window_start, window_end, window_label, claimable, points)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, 0, $14)
+            "#,
+        )
+        .bind(chore_id)
+        .bind(&template.description)
+        .bind(user_id)
+        .bind(template.reward)
+        .bind(template.recurrence)
+        .bind(&template.recurrence_weekdays)
+        .bind(template.recurrence_interval)
+        .bind(template.recurrence_day)
+        .bind(recurrence_start)
+        .bind(payload.due_date)
+        .bind(template.window_start)
+        .bind(template.window_end)
+        .bind(&template.window_label)
+        .bind(template.points)
+        .execute(&mut *tx)
+        .await?;
+
+        let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+            .bind(chore_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        chores.push(chore);
+    }
+
+    tx.commit().await?;
+
+    // Spawn today's occurrences right away instead of waiting for the next refresh
+    if template.recurrence.is_some()
+        && let Err(e) = background::generate_chore_occurrences(&state).await
+    {
+        tracing::warn!(error = ?e, "failed to generate chore occurrences");
+    }
+
+    Ok(Json(chores))
+}
+
+pub async fn save_chore_as_template(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<SaveTemplateSchema>,
+) -> Result<Json<ChoreTemplate>, AppError> {
+    require_admin(&auth)?;
+
+    let chore = query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(AppError::InvalidInput("Chore not found".to_string()))?;
+
+    // An occurrence takes its schedule from the series it belongs to
+    let source = match chore.series_id {
+        Some(series_id) => query_as::<_, Chore>("SELECT * FROM chores WHERE id = $1")
+            .bind(series_id)
+            .fetch_optional(&state.db)
+            .await?
+            .unwrap_or(chore),
+        None => chore,
+    };
+
+    let now = chrono::Utc::now();
+    let template = ChoreTemplate {
+        id: Uuid::new_v4(),
+        description: source.description,
+        reward: source.reward,
+        points: source.points,
+        recurrence: source.recurrence,
+        recurrence_weekdays: source.recurrence_weekdays,
+        recurrence_interval: source.recurrence_interval,
+        recurrence_day: source.recurrence_day,
+        window_start: source.window_start,
+        window_end: source.window_end,
+        window_label: source.window_label,
+        min_age: payload.min_age,
+        max_age: payload.max_age,
+        created_at: now,
+        updated_at: now,
+    };
+
+    validate_template(&template)?;
+
+    let mut conn = state.db.acquire().await?;
+    let template = insert_template(&mut conn, &template).await?;
+
+    Ok(Json(template))
+}
@@ -9,7 +9,7 @@ use uuid::Uuid;
 
 use crate::{
     error::AppError,
-    models::user::{User, CreateUserSchema, UpdateUserSchema, UserRole, ChangePasswordSchema},
+    models::user::{User, CreateUserSchema, UpdateUserSchema, UserRole, ChangePasswordSchema, ChangePinSchema},
     state::AppState,
     utils::{jwt::hash_password, auth_helpers::require_admin},
     middleware::auth::AuthUser,
@@ -207,5 +207,42 @@ pub async fn change_password(
         return Err(AppError::UserNotFound);
     }
 
+    Ok(StatusCode::OK)
+}
+
+pub async fn change_pin(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    auth: AuthUser,
+    Json(payload): Json<ChangePinSchema>,
+) -> Result<StatusCode, AppError> {
+    // Only admin or self can change a PIN
+    if !auth.is_admin() && auth.user_id != id {
+        return Err(AppError::AuthError);
+    }
+
+    // A missing PIN removes it, turning off display check-off for the user
+    let pin_hash = match payload.pin {
+        Some(pin) => {
+            if !(4..=8).contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+                return Err(AppError::InvalidInput("PIN must be 4 to 8 digits".to_string()));
+            }
+            Some(hash_password(&pin)?)
+        }
+        None => None,
+    };
+
+    let result = sqlx::query(
+        "UPDATE users SET pin_hash = $1, updated_at = datetime('now') WHERE id = $2"
+    )
+        .bind(pin_hash)
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::UserNotFound);
+    }
+
     Ok(StatusCode::OK)
 }
\ No newline at end of file
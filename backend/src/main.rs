@@ -7,6 +7,7 @@ mod utils;
 mod background;
 
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{get, post, put, delete},
     middleware as axum_middleware,
     Router,
@@ -16,13 +17,14 @@ use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use dashmap::DashMap;
 use tower_http::services::{ServeDir, ServeFile};
 use tower_http::set_header::SetResponseHeaderLayer;
 use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer, key_extractor::SmartIpKeyExtractor};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::state::AppState;
-use crate::handlers::{auth, user, allowance, settings, calendar, backup, display, chore, weather, google_photos};
+use crate::handlers::{auth, user, allowance, settings, calendar, backup, display, chore, weather, google_photos, reward, routine, template, swap, away, goal, spending};
 
 fn env_bool(key: &str) -> bool {
     matches!(
@@ -40,6 +42,47 @@ async fn get_setting(pool: &sqlx::SqlitePool, key: &str) -> String {
         .unwrap_or_default()
 }
 
+/// `backend verify-ledger [--repair]`: check allowance running balances.
+///
+/// Exits non-zero when balances are wrong and weren't repaired.
+async fn verify_ledger_command(pool: &sqlx::SqlitePool, repair: bool) -> i32 {
+    let result = async {
+        let mut tx = pool.begin().await?;
+        let check = utils::ledger::verify_ledger(&mut tx, repair).await?;
+        tx.commit().await?;
+        Ok::<_, error::AppError>(check)
+    }.await;
+
+    let check = match result {
+        Ok(check) => check,
+        Err(e) => {
+            eprintln!("Ledger check failed: {:?}", e);
+            return 2;
+        }
+    };
+
+    for m in &check.mismatches {
+        println!(
+            "user {} seq {} (entry {}): stored balance {} expected {}",
+            m.user_id, m.seq, m.entry_id, m.stored_balance, m.expected_balance
+        );
+    }
+
+    for entry_id in &check.out_of_order {
+        println!("entry {} is timestamped before the entry preceding it", entry_id);
+    }
+
+    println!(
+        "Checked {} entries for {} users: {} mismatched balances{}",
+        check.entries_checked,
+        check.users_checked,
+        check.mismatches.len(),
+        if check.repaired { ", repaired" } else { "" }
+    );
+
+    if check.mismatches.is_empty() || check.repaired { 0 } else { 1 }
+}
+
 #[tokio::main]
 async fn main() {
     // Initialize tracing
@@ -79,6 +122,12 @@ async fn main() {
         .await
         .expect("Failed to run migrations");
 
+    // Maintenance subcommands work on the database and exit without serving
+    if std::env::args().nth(1).as_deref() == Some("verify-ledger") {
+        let repair = std::env::args().skip(2).any(|a| a == "--repair");
+        std::process::exit(verify_ledger_command(&pool, repair).await);
+    }
+
     // Load settings from DB or Env
     let mut jwt_secret = get_setting(&pool, "jwt_secret").await;
     if jwt_secret.is_empty() {
@@ -138,6 +187,7 @@ async fn main() {
         google_oauth_redirect_uri: Arc::new(RwLock::new(google_oauth_redirect_uri)),
         base_url: Arc::new(RwLock::new(base_url)),
         photos_dir,
+        pin_attempts: Arc::new(DashMap::new()),
     });
 
     // Configure rate limiting for auth endpoints
@@ -162,10 +212,34 @@ async fn main() {
         .route("/users", get(user::get_users).post(user::create_user))
         .route("/users/{id}", get(user::get_user).put(user::update_user).delete(user::delete_user))
         .route("/users/{id}/password", put(user::change_password))
+        .route("/users/{id}/pin", put(user::change_pin))
+        .route("/users/{id}/away", get(away::list_away_periods).post(away::create_away_period))
+        .route("/users/{id}/away/{period_id}", delete(away::delete_away_period))
         // Allowance routes
         .route("/allowance/balances", get(allowance::get_balances))
+        .route("/allowance/schedules", get(allowance::list_schedules).post(allowance::create_schedule))
+        .route("/allowance/schedules/{id}", put(allowance::update_schedule).delete(allowance::delete_schedule))
+        .route("/allowance/schedules/{id}/payouts", get(allowance::list_payouts))
         .route("/allowance/{user_id}", get(allowance::get_ledger))
+        .route("/allowance/{user_id}/ledger", get(allowance::get_ledger_page))
         .route("/allowance/{user_id}/transaction", post(allowance::add_transaction))
+        .route("/allowance/{user_id}/buckets", get(allowance::list_buckets).post(allowance::create_bucket))
+        .route("/allowance/{user_id}/buckets/transfer", post(allowance::transfer_bucket))
+        .route("/goals", get(goal::list_goals).post(goal::create_goal))
+        .route("/goals/{id}", put(goal::update_goal).delete(goal::delete_goal))
+        .route("/allowance/interest", get(allowance::list_interest_rates).post(allowance::create_interest_rate))
+        .route("/allowance/interest/preview", get(allowance::preview_interest))
+        .route("/allowance/interest/{id}", put(allowance::update_interest_rate).delete(allowance::delete_interest_rate))
+        .route("/allowance/interest/{id}/payouts", get(allowance::list_interest_payouts))
+        .route("/allowance/transactions/{id}/void", post(allowance::void_transaction))
+        .route("/allowance/ledger/verify", post(allowance::verify_ledger))
+        .route("/allowance/{user_id}/export", get(allowance::export_ledger))
+        .route("/allowance/requests", get(spending::list_requests).post(spending::create_request))
+        .route("/allowance/requests/{id}/approve", post(spending::approve_request))
+        .route("/allowance/requests/{id}/deny", post(spending::deny_request))
+        .route("/allowance/requests/{id}/cancel", post(spending::cancel_request))
+        .route("/allowance/transfers", get(allowance::list_transfers).post(allowance::create_transfer))
+        .route("/allowance/buckets/{id}", put(allowance::update_bucket).delete(allowance::delete_bucket))
         // Settings routes
         .route("/settings", get(settings::get_settings).put(settings::update_settings))
         // Calendar routes
@@ -182,6 +256,8 @@ async fn main() {
         .route("/display/tokens", get(display::list_tokens).post(display::create_token))
         .route("/display/tokens/{id}", delete(display::delete_token))
         .route("/display/data", get(display::get_display_data))
+        .route("/display/chores/{id}/complete", post(chore::display_complete_chore))
+        .route("/display/routines/{id}/steps/{step_id}/check", post(routine::display_check_routine_step))
         // Google Photos routes
         .route("/google-photos/start", post(google_photos::start_google_oauth))
         .route("/google-photos/callback", get(google_photos::google_oauth_callback))
@@ -190,8 +266,52 @@ async fn main() {
         .route("/google-photos/disconnect", post(google_photos::disconnect_google_photos))
         // Chore routes
         .route("/chores", get(chore::list_chores).post(chore::create_chore))
+        .route("/chores/recurring", get(chore::list_recurring_chores))
+        .route("/chores/open", get(chore::list_open_chores))
+        .route("/chores/pending", get(chore::list_pending_reviews))
+        .route("/chores/stats", get(chore::get_chore_stats))
         .route("/chores/{id}", put(chore::update_chore).delete(chore::delete_chore))
         .route("/chores/{id}/toggle", put(chore::toggle_complete))
+        .route("/chores/{id}/history", get(chore::get_chore_history))
+        .route("/chores/{id}/claim", post(chore::claim_chore))
+        .route("/chores/{id}/release", post(chore::release_chore))
+        .route("/chores/{id}/approve", post(chore::approve_chore))
+        .route("/chores/{id}/reject", post(chore::reject_chore))
+        .route("/chores/{id}/penalty/waive", post(chore::waive_penalty))
+        .route(
+            "/chores/{id}/proof",
+            get(chore::get_chore_proof)
+                .post(chore::upload_chore_proof)
+                .layer(DefaultBodyLimit::max(chore::MAX_PROOF_BYTES)),
+        )
+        .route("/chores/{id}/rotation", get(chore::get_rotation).put(chore::update_rotation))
+        .route("/chores/{id}/rotation/preview", get(chore::preview_rotation))
+        .route("/chores/{id}/template", post(template::save_chore_as_template))
+        // Chore swap routes
+        .route("/chore-swaps", get(swap::list_swaps).post(swap::create_swap))
+        .route("/chore-swaps/{id}/accept", post(swap::accept_swap))
+        .route("/chore-swaps/{id}/decline", post(swap::decline_swap))
+        .route("/chore-swaps/{id}/cancel", post(swap::cancel_swap))
+        .route("/chore-swaps/{id}/confirm", post(swap::confirm_swap))
+        .route("/chore-swaps/{id}/reject", post(swap::reject_swap))
+        // Chore template routes
+        .route("/chore-templates", get(template::list_templates).post(template::create_template))
+        .route("/chore-templates/{id}", put(template::update_template).delete(template::delete_template))
+        .route("/chore-templates/{id}/assign", post(template::assign_template))
+        // Rewards and points routes
+        .route("/rewards", get(reward::list_rewards).post(reward::create_reward))
+        .route("/rewards/redemptions", get(reward::list_redemptions))
+        .route("/rewards/redemptions/{id}/approve", post(reward::approve_redemption))
+        .route("/rewards/redemptions/{id}/deny", post(reward::deny_redemption))
+        .route("/rewards/{id}", put(reward::update_reward).delete(reward::delete_reward))
+        .route("/rewards/{id}/redeem", post(reward::redeem_reward))
+        .route("/points/balances", get(reward::get_points_balances))
+        .route("/points/{user_id}", get(reward::get_points_ledger))
+        .route("/points/{user_id}/transaction", post(reward::add_points_transaction))
+        // Routine routes
+        .route("/routines", get(routine::list_routines).post(routine::create_routine))
+        .route("/routines/{id}", put(routine::update_routine).delete(routine::delete_routine))
+        .route("/routines/{id}/steps/{step_id}/toggle", put(routine::toggle_routine_step))
         // Static files
         .route("/photos/{filename}", get(google_photos::get_photo))
         .layer(axum_middleware::from_fn_with_state(state.clone(), crate::middleware::session::sliding_session_middleware));
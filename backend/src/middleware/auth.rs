@@ -56,3 +56,29 @@ impl FromRequestParts<Arc<AppState>> for AuthUser {
         })
     }
 }
+
+/// Display (kiosk) extractor - authenticated by the X-Display-Token header
+pub struct DisplayAuth {
+    pub token_id: Uuid,
+}
+
+impl FromRequestParts<Arc<AppState>> for DisplayAuth {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get("X-Display-Token")
+            .ok_or(AppError::AuthError)?
+            .to_str()
+            .map_err(|_| AppError::AuthError)?;
+
+        let token_id: Uuid = sqlx::query_scalar("SELECT id FROM display_tokens WHERE token = $1")
+            .bind(token)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or(AppError::AuthError)?;
+
+        Ok(DisplayAuth { token_id })
+    }
+}
@@ -0,0 +1,583 @@
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+use rust_decimal::{prelude::ToPrimitive, Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::user::AllowanceTransaction;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutFrequency {
+    Weekly,  // On the schedule's weekday
+    Monthly, // On the schedule's day of the month
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct AllowanceSchedule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub frequency: PayoutFrequency,
+    pub weekday: Option<String>,
+    pub month_day: Option<i64>,
+    pub start_date: NaiveDate,
+    pub description: Option<String>,
+    pub catch_up: bool,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AllowanceSchedule {
+    /// Check if the schedule pays out on the given date
+    pub fn pays_on(&self, date: NaiveDate) -> bool {
+        if date < self.start_date {
+            return false;
+        }
+
+        match self.frequency {
+            PayoutFrequency::Weekly => self
+                .weekday
+                .as_deref()
+                .and_then(|w| w.parse::<Weekday>().ok())
+                .is_some_and(|w| date.weekday() == w),
+            PayoutFrequency::Monthly => {
+                // Days past the end of a short month fall on its last day
+                let day = self.month_day.unwrap_or(1).clamp(1, 31) as u32;
+                let last_day = (28..=31)
+                    .rev()
+                    .find(|d| date.with_day(*d).is_some())
+                    .unwrap_or(28);
+                date.day() == day.min(last_day)
+            }
+        }
+    }
+
+    /// Pay dates from `from` through `to`, inclusive
+    pub fn pay_dates(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        from.iter_days()
+            .take_while(|d| *d <= to)
+            .filter(|d| self.pays_on(*d))
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AllowancePayout {
+    pub schedule_id: Uuid,
+    pub period_date: NaiveDate,
+    pub entry_id: Option<Uuid>,
+    pub skipped: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateScheduleSchema {
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub frequency: PayoutFrequency,
+    pub weekday: Option<String>,
+    pub month_day: Option<i64>,
+    pub start_date: Option<NaiveDate>,
+    pub description: Option<String>,
+    pub catch_up: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateScheduleSchema {
+    pub amount: Option<i64>,
+    pub frequency: Option<PayoutFrequency>,
+    pub weekday: Option<String>,
+    pub month_day: Option<i64>,
+    pub start_date: Option<NaiveDate>,
+    pub description: Option<String>,
+    pub catch_up: Option<bool>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct AllowanceBucket {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub split_percent: i64,
+    pub position: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AllowanceAllocation {
+    pub entry_id: Uuid,
+    pub bucket_id: Uuid,
+    pub amount: i64,
+}
+
+/// Spread an incoming amount across buckets by their split percentages.
+///
+/// Shares are rounded down; whatever is left, including any percentage not
+/// assigned to a bucket, goes to the first bucket. Buckets must be in order.
+pub fn split_amount(buckets: &[AllowanceBucket], amount: i64) -> Vec<(Uuid, i64)> {
+    let Some(first) = buckets.first() else {
+        return vec![];
+    };
+
+    let mut shares: Vec<(Uuid, i64)> = buckets
+        .iter()
+        // Widened so large amounts can't overflow; a share is never bigger than the amount
+        .map(|b| (b.id, (amount as i128 * b.split_percent.clamp(0, 100) as i128 / 100) as i64))
+        .collect();
+
+    let remainder = amount - shares.iter().map(|(_, share)| share).sum::<i64>();
+    if let Some(share) = shares.iter_mut().find(|(id, _)| *id == first.id) {
+        share.1 += remainder;
+    }
+
+    shares.retain(|(_, share)| *share != 0);
+    shares
+}
+
+/// Take an outgoing amount out of buckets when it isn't aimed at one.
+///
+/// Money not in any bucket goes first, then each bucket in order up to what it
+/// holds; anything beyond that overdraws the first bucket. `amount` is negative
+/// and buckets must be in order.
+pub fn spread_debit(buckets: &[BucketBalance], unallocated: i64, amount: i64) -> Vec<(Uuid, i64)> {
+    let mut owed = -(amount as i128) - unallocated.max(0) as i128;
+    let mut shares: Vec<(Uuid, i64)> = Vec::new();
+
+    for bucket in buckets {
+        if owed <= 0 {
+            break;
+        }
+        let take = owed.min(bucket.balance.max(0) as i128);
+        owed -= take;
+        shares.push((bucket.bucket_id, -(take as i64)));
+    }
+
+    if owed > 0
+        && let Some(first) = shares.first_mut()
+    {
+        first.1 -= owed as i64;
+    }
+
+    shares.retain(|(_, share)| *share != 0);
+    shares
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct BucketBalance {
+    pub bucket_id: Uuid,
+    pub name: String,
+    pub split_percent: i64,
+    pub position: i64,
+    pub balance: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBucketSchema {
+    pub name: String,
+    pub split_percent: Option<i64>,
+    pub position: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateBucketSchema {
+    pub name: Option<String>,
+    pub split_percent: Option<i64>,
+    pub position: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BucketTransferSchema {
+    pub from_bucket_id: Option<Uuid>, // None moves unallocated money
+    pub to_bucket_id: Uuid,
+    pub amount: i64,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct InterestRate {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub bucket_id: Option<Uuid>,
+    pub annual_rate: String,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl InterestRate {
+    /// The stored percentage as an exact decimal
+    pub fn rate(&self) -> Decimal {
+        self.annual_rate.parse().unwrap_or_default()
+    }
+}
+
+/// One month's interest on a balance in cents at an annual percentage rate.
+///
+/// Only positive balances earn interest. Fractions of a cent are rounded to
+/// the nearest cent, with exact halves going to the even cent (banker's
+/// rounding) so repeated payouts don't drift up or down.
+pub fn monthly_interest(balance: i64, annual_rate: Decimal) -> i64 {
+    if balance <= 0 || annual_rate <= Decimal::ZERO {
+        return 0;
+    }
+
+    (Decimal::from(balance) * annual_rate / Decimal::from(1200))
+        .round_dp_with_strategy(0, RoundingStrategy::MidpointNearestEven)
+        .to_i64()
+        .unwrap_or(0)
+}
+
+/// Compound a balance monthly, paying on the first of each month after `from`
+pub fn project_interest(
+    balance: i64,
+    annual_rate: Decimal,
+    from: NaiveDate,
+    months: u32,
+) -> Vec<InterestProjection> {
+    let first_of_month = from.with_day(1).unwrap_or(from);
+    let mut balance = balance;
+
+    (1..=months)
+        .filter_map(|m| first_of_month.checked_add_months(Months::new(m)))
+        .map(|month| {
+            let interest = monthly_interest(balance, annual_rate);
+            balance = balance.saturating_add(interest);
+            InterestProjection { month, interest, balance }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct InterestPayout {
+    pub rate_id: Uuid,
+    pub period_date: NaiveDate,
+    pub entry_id: Option<Uuid>,
+    pub balance: i64,
+    pub amount: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InterestProjection {
+    pub month: NaiveDate,
+    pub interest: i64,
+    pub balance: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InterestPreview {
+    pub annual_rate: Decimal,
+    pub starting_balance: i64,
+    pub total_interest: i64,
+    pub months: Vec<InterestProjection>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInterestSchema {
+    pub user_id: Uuid,
+    pub bucket_id: Option<Uuid>,
+    pub annual_rate: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateInterestSchema {
+    pub annual_rate: Option<Decimal>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InterestPreviewQuery {
+    pub user_id: Uuid,
+    pub bucket_id: Option<Uuid>,
+    pub annual_rate: Option<Decimal>, // Defaults to the configured rate
+    pub months: Option<u32>,
+    pub balance: Option<i64>,         // Defaults to the current balance
+}
+
+#[derive(Debug, Serialize)]
+pub struct LedgerMismatch {
+    pub user_id: Uuid,
+    pub seq: i64,
+    pub entry_id: Uuid,
+    pub stored_balance: i64,
+    pub expected_balance: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LedgerCheck {
+    pub users_checked: i64,
+    pub entries_checked: i64,
+    pub mismatches: Vec<LedgerMismatch>,
+    pub out_of_order: Vec<Uuid>, // entries stamped earlier than the one before them
+    pub repaired: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyLedgerSchema {
+    pub repair: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv, // Spreadsheets
+    Ofx, // Bank statement for budgeting apps
+    Qif, // Older Quicken-style imports
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ofx => "application/x-ofx",
+            ExportFormat::Qif => "application/qif",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ofx => "ofx",
+            ExportFormat::Qif => "qif",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportLedgerQuery {
+    pub format: ExportFormat,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LedgerQuery {
+    pub before: Option<i64>, // Cursor: only entries with a lower seq
+    pub limit: Option<i64>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub search: Option<String>,
+}
+
+/// Totals over every entry matching the filters, not just the current page
+#[derive(Debug, Serialize, FromRow)]
+pub struct LedgerSummary {
+    pub total_count: i64,
+    pub credits: i64,
+    pub debits: i64,
+    pub net: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LedgerPage {
+    pub entries: Vec<AllowanceTransaction>,
+    pub next_cursor: Option<i64>,
+    pub summary: LedgerSummary,
+}
+
+/// Turn free text into an FTS5 query matching every word as a prefix
+pub fn fts_query(search: &str) -> Option<String> {
+    let terms: Vec<String> = search
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AllowanceTransfer {
+    pub id: Uuid,
+    pub from_user_id: Option<Uuid>, // None once that person is deleted
+    pub from_user_name: Option<String>,
+    pub to_user_id: Option<Uuid>,
+    pub to_user_name: Option<String>,
+    pub amount: i64,
+    pub description: Option<String>,
+    pub debit_entry_id: Option<Uuid>,
+    pub credit_entry_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTransferSchema {
+    pub from_user_id: Option<Uuid>, // Defaults to the caller
+    pub to_user_id: Uuid,
+    pub amount: i64,
+    pub description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn monthly_schedule(month_day: i64) -> AllowanceSchedule {
+        AllowanceSchedule {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            amount: 500,
+            frequency: PayoutFrequency::Monthly,
+            weekday: None,
+            month_day: Some(month_day),
+            start_date: date(2026, 1, 1),
+            description: None,
+            catch_up: false,
+            active: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn bucket(split_percent: i64) -> AllowanceBucket {
+        AllowanceBucket {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: format!("{}%", split_percent),
+            split_percent,
+            position: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn balance(bucket_id: Uuid, balance: i64) -> BucketBalance {
+        BucketBalance { bucket_id, name: String::new(), split_percent: 0, position: 0, balance }
+    }
+
+    #[test]
+    fn late_month_days_fall_on_the_last_day_of_short_months() {
+        let schedule = monthly_schedule(31);
+
+        assert!(schedule.pays_on(date(2026, 1, 31)));
+        assert!(schedule.pays_on(date(2026, 2, 28)));
+        assert!(!schedule.pays_on(date(2026, 2, 27)));
+        assert!(schedule.pays_on(date(2026, 4, 30)));
+        assert!(schedule.pays_on(date(2028, 2, 29)));
+        assert!(!schedule.pays_on(date(2028, 2, 28)));
+    }
+
+    #[test]
+    fn pay_dates_are_once_a_month_from_the_start_date() {
+        let mut schedule = monthly_schedule(30);
+        schedule.start_date = date(2026, 1, 15);
+
+        assert_eq!(
+            schedule.pay_dates(date(2026, 1, 1), date(2026, 3, 31)),
+            vec![date(2026, 1, 30), date(2026, 2, 28), date(2026, 3, 30)]
+        );
+        assert!(schedule.pay_dates(date(2025, 12, 1), date(2025, 12, 31)).is_empty());
+    }
+
+    #[test]
+    fn weekly_schedules_pay_on_their_weekday() {
+        let mut schedule = monthly_schedule(1);
+        schedule.frequency = PayoutFrequency::Weekly;
+        schedule.weekday = Some("fri".to_string());
+
+        assert_eq!(
+            schedule.pay_dates(date(2026, 1, 1), date(2026, 1, 14)),
+            vec![date(2026, 1, 2), date(2026, 1, 9)]
+        );
+    }
+
+    #[test]
+    fn split_remainders_go_to_the_first_bucket() {
+        let buckets = vec![bucket(33), bucket(33), bucket(34)];
+
+        let shares = split_amount(&buckets, 100);
+        assert_eq!(shares, vec![(buckets[0].id, 33), (buckets[1].id, 33), (buckets[2].id, 34)]);
+
+        let shares = split_amount(&buckets, 10);
+        assert_eq!(shares, vec![(buckets[0].id, 4), (buckets[1].id, 3), (buckets[2].id, 3)]);
+        assert_eq!(shares.iter().map(|(_, s)| s).sum::<i64>(), 10);
+    }
+
+    #[test]
+    fn unassigned_percentages_go_to_the_first_bucket() {
+        let buckets = vec![bucket(0), bucket(50)];
+
+        assert_eq!(split_amount(&buckets, 101), vec![(buckets[0].id, 51), (buckets[1].id, 50)]);
+        assert_eq!(split_amount(&buckets[..1], 0), vec![]);
+        assert!(split_amount(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn split_handles_the_largest_amounts() {
+        let buckets = vec![bucket(70), bucket(30)];
+        let shares = split_amount(&buckets, i64::MAX);
+
+        assert_eq!(shares.iter().map(|(_, s)| *s as i128).sum::<i128>(), i64::MAX as i128);
+    }
+
+    #[test]
+    fn debits_use_unallocated_money_then_buckets_in_order() {
+        let (spend, save) = (Uuid::new_v4(), Uuid::new_v4());
+        let buckets = vec![balance(spend, 300), balance(save, 500)];
+
+        assert!(spread_debit(&buckets, 200, -150).is_empty());
+        assert_eq!(spread_debit(&buckets, 200, -400), vec![(spend, -200)]);
+        assert_eq!(spread_debit(&buckets, 0, -600), vec![(spend, -300), (save, -300)]);
+    }
+
+    #[test]
+    fn debits_beyond_every_bucket_overdraw_the_first() {
+        let (spend, save) = (Uuid::new_v4(), Uuid::new_v4());
+        let buckets = vec![balance(spend, 100), balance(save, 50)];
+
+        assert_eq!(spread_debit(&buckets, 0, -200), vec![(spend, -150), (save, -50)]);
+        assert_eq!(spread_debit(&[balance(spend, 0)], 0, -25), vec![(spend, -25)]);
+    }
+
+    #[test]
+    fn interest_rounds_exact_halves_to_the_even_cent() {
+        let rate = |r: &str| r.parse::<Decimal>().unwrap();
+
+        assert_eq!(monthly_interest(100, rate("6")), 0); // 0.5
+        assert_eq!(monthly_interest(100, rate("18")), 2); // 1.5
+        assert_eq!(monthly_interest(250, rate("12")), 2); // 2.5
+        assert_eq!(monthly_interest(350, rate("12")), 4); // 3.5
+        assert_eq!(monthly_interest(10_000, rate("5.25")), 44); // 43.75
+    }
+
+    #[test]
+    fn interest_is_only_paid_on_positive_balances() {
+        assert_eq!(monthly_interest(0, Decimal::from(5)), 0);
+        assert_eq!(monthly_interest(-10_000, Decimal::from(5)), 0);
+        assert_eq!(monthly_interest(10_000, Decimal::ZERO), 0);
+    }
+
+    #[test]
+    fn projections_compound_on_the_first_of_each_month() {
+        let months = project_interest(10_000, Decimal::from(12), date(2026, 1, 15), 3);
+
+        let summary: Vec<_> = months.iter().map(|m| (m.month, m.interest, m.balance)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (date(2026, 2, 1), 100, 10_100),
+                (date(2026, 3, 1), 101, 10_201),
+                (date(2026, 4, 1), 102, 10_303),
+            ]
+        );
+    }
+
+    #[test]
+    fn search_terms_become_quoted_prefixes() {
+        assert_eq!(fts_query("pocket  money").as_deref(), Some("\"pocket\"* \"money\"*"));
+        assert_eq!(fts_query("say \"hi\"").as_deref(), Some("\"say\"* \"\"\"hi\"\"\"*"));
+        assert_eq!(fts_query("OR NEAR(a)").as_deref(), Some("\"OR\"* \"NEAR(a)\"*"));
+        assert_eq!(fts_query("   "), None);
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct AwayPeriod {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub note: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AwayPeriod {
+    /// Check if this period has the given user away on the given date
+    pub fn covers(&self, user_id: Uuid, date: NaiveDate) -> bool {
+        self.user_id == user_id && self.start_date <= date && date <= self.end_date
+    }
+}
+
+/// Check if any of the periods has the given user away on the given date
+pub fn is_away(periods: &[AwayPeriod], user_id: Uuid, date: NaiveDate) -> bool {
+    periods.iter().any(|p| p.covers(user_id, date))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAwayPeriodSchema {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub note: Option<String>,
+}
@@ -1,6 +1,15 @@
 use serde::{Deserialize, Serialize};
 use crate::models::{
     user::{BackupUser, AllowanceTransaction},
+    allowance::{
+        AllowanceAllocation, AllowanceBucket, AllowancePayout, AllowanceSchedule, AllowanceTransfer,
+        InterestPayout, InterestRate,
+    },
+    reward::{PointsTransaction, Redemption, Reward},
+    away::AwayPeriod,
+    goal::SavingsGoal,
+    spending::SpendingRequest,
+    routine::{Routine, RoutineCompletion, RoutineStep, RoutineStepCheck},
     settings::Setting,
     calendar::Calendar,
 };
@@ -11,6 +20,41 @@ pub struct BackupData {
     pub settings: Vec<Setting>,
     pub calendars: Vec<Calendar>,
     pub allowance_ledger: Vec<AllowanceTransaction>,
+    #[serde(default)] // Missing from backups taken before transfers existed
+    pub allowance_transfers: Vec<AllowanceTransfer>,
+    // Tables added after the first backups default to empty as well
+    #[serde(default)]
+    pub rewards: Vec<Reward>,
+    #[serde(default)]
+    pub points_ledger: Vec<PointsTransaction>,
+    #[serde(default)]
+    pub reward_redemptions: Vec<Redemption>,
+    #[serde(default)]
+    pub routines: Vec<Routine>,
+    #[serde(default)]
+    pub routine_steps: Vec<RoutineStep>,
+    #[serde(default)]
+    pub routine_step_checks: Vec<RoutineStepCheck>,
+    #[serde(default)]
+    pub routine_completions: Vec<RoutineCompletion>,
+    #[serde(default)]
+    pub away_periods: Vec<AwayPeriod>,
+    #[serde(default)]
+    pub allowance_schedules: Vec<AllowanceSchedule>,
+    #[serde(default)]
+    pub allowance_payouts: Vec<AllowancePayout>,
+    #[serde(default)]
+    pub allowance_buckets: Vec<AllowanceBucket>,
+    #[serde(default)]
+    pub allowance_allocations: Vec<AllowanceAllocation>,
+    #[serde(default)]
+    pub savings_goals: Vec<SavingsGoal>,
+    #[serde(default)]
+    pub interest_rates: Vec<InterestRate>,
+    #[serde(default)]
+    pub interest_payouts: Vec<InterestPayout>,
+    #[serde(default)]
+    pub spending_requests: Vec<SpendingRequest>,
     pub version: u32,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
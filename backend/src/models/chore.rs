@@ -1,7 +1,48 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::models::away::{is_away, AwayPeriod};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,      // Every day
+    Weekly,     // On the weekdays listed in recurrence_weekdays
+    EveryNDays, // Every recurrence_interval days from recurrence_start
+    Monthly,    // On recurrence_day of each month
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    Pending,  // Marked done by a child, waiting on a parent
+    Approved, // Accepted by a parent and counted as complete
+    Rejected, // Sent back by a parent
+}
+
+/// The moment a chore becomes late: the end of its window, or the end of the due date
+pub fn due_deadline(due_date: Option<NaiveDate>, window_end: Option<NaiveTime>) -> Option<NaiveDateTime> {
+    let due_date = due_date?;
+    Some(match window_end {
+        Some(end) => due_date.and_time(end),
+        None => due_date.succ_opt()?.and_time(NaiveTime::MIN),
+    })
+}
+
+/// Parse a comma separated weekday list such as "mon,wed,fri".
+pub fn parse_weekdays(value: &str) -> Result<Vec<Weekday>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse::<Weekday>().map_err(|_| format!("Invalid weekday: {}", d)))
+        .collect()
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct Chore {
     pub id: Uuid,
@@ -9,10 +50,105 @@ pub struct Chore {
     pub assigned_to: Option<Uuid>,
     pub reward: Option<i64>,
     pub completed: bool,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub recurrence_start: Option<NaiveDate>,
+    pub series_id: Option<Uuid>,
+    pub occurrence_date: Option<NaiveDate>,
+    pub points: Option<i64>,
+    pub points_entry_id: Option<Uuid>,
+    pub reward_entry_id: Option<Uuid>,
+    pub review_status: Option<ReviewStatus>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub review_note: Option<String>,
+    pub rotation_period_days: Option<i64>,
+    pub rotation_start: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub claimable: bool,
+    pub claim_hours: Option<i64>,
+    pub claimed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub claim_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub penalty: Option<i64>,
+    pub penalty_grace_minutes: Option<i64>,
+    pub penalty_entry_id: Option<Uuid>,
+    pub penalty_waived_by: Option<Uuid>,
+    pub penalty_waived_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub proof_photo: Option<String>,
+    pub proof_uploaded_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl Chore {
+    /// Check if this chore is a recurring series rather than a single item
+    pub fn is_series(&self) -> bool {
+        self.recurrence.is_some()
+    }
+
+    /// The moment a missed chore's penalty is charged: its deadline plus the grace period
+    pub fn penalty_deadline(&self) -> Option<NaiveDateTime> {
+        let grace = Duration::minutes(self.penalty_grace_minutes.unwrap_or(0).max(0));
+        Some(due_deadline(self.due_date, self.window_end)? + grace)
+    }
+
+    /// Check if a recurring chore has an occurrence on the given date
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        let Some(recurrence) = self.recurrence else {
+            return false;
+        };
+
+        let start = self
+            .recurrence_start
+            .unwrap_or_else(|| self.created_at.date_naive());
+        if date < start {
+            return false;
+        }
+
+        match recurrence {
+            Recurrence::Daily => true,
+            Recurrence::Weekly => self
+                .recurrence_weekdays
+                .as_deref()
+                .and_then(|w| parse_weekdays(w).ok())
+                .is_some_and(|days| days.contains(&date.weekday())),
+            Recurrence::EveryNDays => {
+                let interval = self.recurrence_interval.unwrap_or(1).max(1);
+                (date - start).num_days() % interval == 0
+            }
+            Recurrence::Monthly => {
+                // Days past the end of a short month fall on its last day
+                let day = self.recurrence_day.unwrap_or(1).clamp(1, 31) as u32;
+                let last_day = (28..=31)
+                    .rev()
+                    .find(|d| date.with_day(*d).is_some())
+                    .unwrap_or(28);
+                date.day() == day.min(last_day)
+            }
+        }
+    }
+
+    /// Position in a rotation of `members` people whose turn it is on the given date
+    pub fn rotation_slot(&self, date: NaiveDate, members: usize) -> Option<usize> {
+        let period = self.rotation_period_days?.max(1);
+        if members == 0 {
+            return None;
+        }
+
+        let start = self
+            .rotation_start
+            .unwrap_or_else(|| self.created_at.date_naive());
+        let periods = (date - start).num_days().max(0) / period;
+
+        Some((periods as usize) % members)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct ChoreWithUser {
     pub id: Uuid,
@@ -20,9 +156,58 @@ pub struct ChoreWithUser {
     pub assigned_to: Option<Uuid>,
     pub assigned_name: Option<String>,
     pub reward: Option<i64>,
+    pub points: Option<i64>,
     pub completed: bool,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub recurrence_start: Option<NaiveDate>,
+    pub series_id: Option<Uuid>,
+    pub occurrence_date: Option<NaiveDate>,
+    pub review_status: Option<ReviewStatus>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewer_name: Option<String>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub review_note: Option<String>,
+    pub rotation_period_days: Option<i64>,
+    pub rotation_start: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub claimable: bool,
+    pub claim_hours: Option<i64>,
+    pub claimed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub claim_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub penalty: Option<i64>,
+    pub penalty_grace_minutes: Option<i64>,
+    pub penalty_entry_id: Option<Uuid>,
+    pub penalty_waived_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub proof_photo: Option<String>,
+    pub proof_uploaded_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[sqlx(skip)]
+    pub overdue: bool,
+}
+
+impl ChoreWithUser {
+    /// Work out the overdue flag as of the given local time.
+    ///
+    /// Chores aren't overdue for someone who is away now or was away on the due date.
+    pub fn with_overdue(mut self, now: NaiveDateTime, away: &[AwayPeriod]) -> Self {
+        let excused = self.assigned_to.is_some_and(|user_id| {
+            is_away(away, user_id, now.date())
+                || self.due_date.is_some_and(|due| is_away(away, user_id, due))
+        });
+
+        self.overdue = !self.completed
+            && !excused
+            && self.review_status != Some(ReviewStatus::Pending)
+            && due_deadline(self.due_date, self.window_end).is_some_and(|due| now > due);
+        self
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -30,6 +215,19 @@ pub struct CreateChoreSchema {
     pub description: String,
     pub assigned_to: Option<Uuid>,
     pub reward: Option<i64>,
+    pub points: Option<i64>,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub recurrence_start: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub claim_hours: Option<i64>,
+    pub penalty: Option<i64>,
+    pub penalty_grace_minutes: Option<i64>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -37,5 +235,301 @@ pub struct UpdateChoreSchema {
     pub description: Option<String>,
     pub assigned_to: Option<Uuid>,
     pub reward: Option<i64>,
+    pub points: Option<i64>,
     pub completed: Option<bool>,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub recurrence_start: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub claim_hours: Option<i64>,
+    pub penalty: Option<i64>,
+    pub penalty_grace_minutes: Option<i64>,
+}
+
+impl UpdateChoreSchema {
+    /// Check the update does nothing but mark the chore done or not done
+    pub fn only_completion(&self) -> bool {
+        // Destructured so a new field has to be considered here
+        let UpdateChoreSchema {
+            description,
+            assigned_to,
+            reward,
+            points,
+            completed: _,
+            recurrence,
+            recurrence_weekdays,
+            recurrence_interval,
+            recurrence_day,
+            recurrence_start,
+            due_date,
+            window_start,
+            window_end,
+            window_label,
+            claim_hours,
+            penalty,
+            penalty_grace_minutes,
+        } = self;
+
+        description.is_none()
+            && assigned_to.is_none()
+            && reward.is_none()
+            && points.is_none()
+            && recurrence.is_none()
+            && recurrence_weekdays.is_none()
+            && recurrence_interval.is_none()
+            && recurrence_day.is_none()
+            && recurrence_start.is_none()
+            && due_date.is_none()
+            && window_start.is_none()
+            && window_end.is_none()
+            && window_label.is_none()
+            && claim_hours.is_none()
+            && penalty.is_none()
+            && penalty_grace_minutes.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReviewChoreSchema {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisplayCompleteChoreSchema {
+    pub pin: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RotationMember {
+    pub position: i64,
+    pub user_id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChoreRotation {
+    pub chore_id: Uuid,
+    pub period_days: Option<i64>,
+    pub start: Option<NaiveDate>,
+    pub members: Vec<RotationMember>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRotationSchema {
+    pub user_ids: Vec<Uuid>,
+    pub period_days: i64,
+    pub start: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RotationPreviewQuery {
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RotationPreview {
+    pub date: NaiveDate,
+    pub user_id: Option<Uuid>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChoreFilter {
+    Today,    // Due today
+    Overdue,  // Past due and not done
+    Upcoming, // Due after today
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListChoresQuery {
+    pub filter: Option<ChoreFilter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ChoreEventType {
+    Completed,
+    Uncompleted,
+    Swapped, // Traded to user_id through a chore swap
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ChoreEvent {
+    pub seq: i64,
+    pub id: Uuid,
+    pub chore_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub event_type: ChoreEventType,
+    pub on_time: Option<bool>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChoreStatsQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChoreStats {
+    pub user_id: Uuid,
+    pub name: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub completed: i64,
+    pub on_time: i64,
+    pub late: i64,
+    pub on_time_rate: Option<f64>,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    pub earnings: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn chore() -> Chore {
+        Chore {
+            id: Uuid::new_v4(),
+            description: "Dishes".to_string(),
+            assigned_to: None,
+            reward: None,
+            completed: false,
+            recurrence: None,
+            recurrence_weekdays: None,
+            recurrence_interval: None,
+            recurrence_day: None,
+            recurrence_start: Some(date(2026, 1, 1)),
+            series_id: None,
+            occurrence_date: None,
+            points: None,
+            points_entry_id: None,
+            reward_entry_id: None,
+            review_status: None,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_note: None,
+            rotation_period_days: None,
+            rotation_start: Some(date(2026, 1, 1)),
+            due_date: None,
+            window_start: None,
+            window_end: None,
+            window_label: None,
+            claimable: false,
+            claim_hours: None,
+            claimed_at: None,
+            claim_expires_at: None,
+            penalty: None,
+            penalty_grace_minutes: None,
+            penalty_entry_id: None,
+            penalty_waived_by: None,
+            penalty_waived_at: None,
+            proof_photo: None,
+            proof_uploaded_at: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rotation_moves_to_the_next_member_each_period() {
+        let mut chore = chore();
+        chore.rotation_period_days = Some(7);
+
+        assert_eq!(chore.rotation_slot(date(2026, 1, 1), 3), Some(0));
+        assert_eq!(chore.rotation_slot(date(2026, 1, 7), 3), Some(0));
+        assert_eq!(chore.rotation_slot(date(2026, 1, 8), 3), Some(1));
+        assert_eq!(chore.rotation_slot(date(2026, 1, 15), 3), Some(2));
+        assert_eq!(chore.rotation_slot(date(2026, 1, 22), 3), Some(0));
+    }
+
+    #[test]
+    fn rotation_starts_with_the_first_member_before_its_start() {
+        let mut chore = chore();
+        chore.rotation_period_days = Some(1);
+
+        assert_eq!(chore.rotation_slot(date(2025, 12, 1), 4), Some(0));
+        assert_eq!(chore.rotation_slot(date(2026, 1, 3), 0), None);
+
+        chore.rotation_period_days = None;
+        assert_eq!(chore.rotation_slot(date(2026, 1, 3), 4), None);
+
+        chore.rotation_period_days = Some(0);
+        assert_eq!(chore.rotation_slot(date(2026, 1, 3), 4), Some(2));
+    }
+
+    #[test]
+    fn monthly_chores_on_late_days_fall_on_the_last_day_of_short_months() {
+        let mut chore = chore();
+        chore.recurrence = Some(Recurrence::Monthly);
+        chore.recurrence_day = Some(31);
+
+        assert!(chore.occurs_on(date(2026, 1, 31)));
+        assert!(chore.occurs_on(date(2026, 2, 28)));
+        assert!(chore.occurs_on(date(2026, 4, 30)));
+        assert!(!chore.occurs_on(date(2026, 4, 29)));
+        assert!(chore.occurs_on(date(2028, 2, 29)));
+    }
+
+    #[test]
+    fn recurring_chores_occur_only_on_their_days() {
+        let mut chore = chore();
+        assert!(!chore.occurs_on(date(2026, 1, 1)));
+
+        chore.recurrence = Some(Recurrence::EveryNDays);
+        chore.recurrence_interval = Some(3);
+        assert!(chore.occurs_on(date(2026, 1, 1)));
+        assert!(chore.occurs_on(date(2026, 1, 4)));
+        assert!(!chore.occurs_on(date(2026, 1, 5)));
+        assert!(!chore.occurs_on(date(2025, 12, 29)));
+
+        chore.recurrence = Some(Recurrence::Weekly);
+        chore.recurrence_weekdays = Some("mon, fri".to_string());
+        assert!(chore.occurs_on(date(2026, 1, 2)));
+        assert!(chore.occurs_on(date(2026, 1, 5)));
+        assert!(!chore.occurs_on(date(2026, 1, 6)));
+    }
+
+    #[test]
+    fn deadlines_are_the_window_end_or_the_end_of_the_due_date() {
+        let due = date(2026, 1, 10);
+        let end = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+
+        assert_eq!(due_deadline(Some(due), Some(end)), Some(due.and_time(end)));
+        assert_eq!(due_deadline(Some(due), None), Some(date(2026, 1, 11).and_time(NaiveTime::MIN)));
+        assert_eq!(due_deadline(None, Some(end)), None);
+    }
+
+    #[test]
+    fn penalties_wait_out_the_grace_period() {
+        let mut chore = chore();
+        chore.due_date = Some(date(2026, 1, 10));
+        chore.window_end = NaiveTime::from_hms_opt(8, 0, 0);
+        chore.penalty_grace_minutes = Some(90);
+
+        assert_eq!(
+            chore.penalty_deadline(),
+            Some(date(2026, 1, 10).and_hms_opt(9, 30, 0).unwrap())
+        );
+
+        chore.penalty_grace_minutes = Some(-30);
+        assert_eq!(
+            chore.penalty_deadline(),
+            Some(date(2026, 1, 10).and_hms_opt(8, 0, 0).unwrap())
+        );
+    }
 }
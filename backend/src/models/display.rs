@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use crate::models::{user::UserBalance, calendar::CalendarPublic, chore::ChoreWithUser};
+use crate::models::{user::UserBalance, calendar::CalendarPublic, chore::ChoreWithUser, reward::UserPoints, routine::RoutineProgress, goal::GoalProgress};
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct DisplayToken {
@@ -21,6 +21,10 @@ pub struct DisplayData {
     pub weather: Option<serde_json::Value>,
     pub calendars: Vec<CalendarPublic>,
     pub allowances: Vec<UserBalance>,
+    pub points: Vec<UserPoints>,
     pub chores: Vec<ChoreWithUser>,
+    pub open_chores: Vec<ChoreWithUser>,
+    pub routines: Vec<RoutineProgress>,
+    pub goals: Vec<GoalProgress>,
     pub background_url: Option<String>,
 }
\ No newline at end of file
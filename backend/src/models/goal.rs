@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct SavingsGoal {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub bucket_id: Option<Uuid>,
+    pub name: String,
+    pub target_amount: i64,
+    pub image_url: Option<String>,
+    pub target_date: Option<NaiveDate>,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GoalProgress {
+    #[serde(flatten)]
+    pub goal: SavingsGoal,
+    pub user_name: String,
+    pub bucket_name: Option<String>,
+    pub saved: i64,
+    pub progress_percent: f64,
+    pub reached: bool,
+    pub projected_date: Option<NaiveDate>,
+    pub on_track: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateGoalSchema {
+    pub user_id: Uuid,
+    pub bucket_id: Option<Uuid>,
+    pub name: String,
+    pub target_amount: i64,
+    pub image_url: Option<String>,
+    pub target_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateGoalSchema {
+    pub bucket_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub target_amount: Option<i64>,
+    pub image_url: Option<String>,
+    pub target_date: Option<NaiveDate>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListGoalsQuery {
+    pub user_id: Option<Uuid>,
+}
@@ -3,4 +3,12 @@ pub mod chore;
 pub mod calendar;
 pub mod settings;
 pub mod display;
-pub mod backup;
\ No newline at end of file
+pub mod backup;
+pub mod reward;
+pub mod routine;
+pub mod template;
+pub mod swap;
+pub mod away;
+pub mod allowance;
+pub mod goal;
+pub mod spending;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Reward {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub cost: i64,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRewardSchema {
+    pub name: String,
+    pub description: Option<String>,
+    pub cost: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRewardSchema {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub cost: Option<i64>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PointsTransaction {
+    pub seq: i64,
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub balance: i64,
+    pub description: String,
+    pub chore_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePointsTransactionSchema {
+    pub amount: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct UserPoints {
+    pub user_id: Uuid,
+    pub name: String,
+    pub points: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RedemptionStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Redemption {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub reward_id: Option<Uuid>,
+    pub reward_name: String,
+    pub cost: i64,
+    pub status: RedemptionStatus,
+    pub note: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub points_entry_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedemptionQuery {
+    pub status: Option<RedemptionStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewRedemptionSchema {
+    pub note: Option<String>,
+}
@@ -0,0 +1,95 @@
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct Routine {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub reward: Option<i64>,
+    pub active: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Routine {
+    /// Check if the routine's time window includes the given time of day
+    pub fn in_window(&self, time: NaiveTime) -> bool {
+        self.window_start.is_none_or(|start| time >= start)
+            && self.window_end.is_none_or(|end| time <= end)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RoutineStepStatus {
+    pub id: Uuid,
+    pub position: i64,
+    pub description: String,
+    pub checked: bool,
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RoutineStep {
+    pub id: Uuid,
+    pub routine_id: Uuid,
+    pub position: i64,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RoutineStepCheck {
+    pub step_id: Uuid,
+    pub check_date: NaiveDate,
+    pub checked_by: Option<Uuid>,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RoutineCompletion {
+    pub routine_id: Uuid,
+    pub completion_date: NaiveDate,
+    pub reward_entry_id: Option<Uuid>,
+    pub completed_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoutineProgress {
+    #[serde(flatten)]
+    pub routine: Routine,
+    pub user_name: String,
+    pub date: NaiveDate,
+    pub in_window: bool,
+    pub steps: Vec<RoutineStepStatus>,
+    pub completed_steps: usize,
+    pub completed: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRoutineSchema {
+    pub user_id: Uuid,
+    pub name: String,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub reward: Option<i64>,
+    pub steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisplayCheckStepSchema {
+    pub pin: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRoutineSchema {
+    pub name: Option<String>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub reward: Option<i64>,
+    pub active: Option<bool>,
+    pub steps: Option<Vec<String>>,
+}
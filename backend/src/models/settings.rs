@@ -18,6 +18,20 @@ pub struct AppSettings {
     pub google_client_id: String,
     pub google_client_secret: String,
 
+    // Days to keep chore proof photos, empty for the default
+    pub chore_proof_retention_days: String,
+
+    // Chore swaps agreed between family members also need a parent's confirmation
+    pub chore_swaps_need_approval: bool,
+
+    // ISO 4217 code and decimal places used when exporting allowance amounts, empty for USD and 2
+    pub currency_code: String,
+    pub currency_decimals: String,
+
+    // What spending requests may do to a negative balance: block, limit (to overdraft_limit cents) or allow
+    pub overdraft_policy: String,
+    pub overdraft_limit: String,
+
     // Indicate if Google account is connected (has refresh token)
     pub google_connected: bool,
 
@@ -54,4 +68,16 @@ pub struct UpdateAppSettingsSchema {
 
     pub google_client_secret: Option<String>,
 
+    pub chore_proof_retention_days: Option<String>,
+
+    pub chore_swaps_need_approval: Option<bool>,
+
+    pub currency_code: Option<String>,
+
+    pub currency_decimals: Option<String>,
+
+    pub overdraft_policy: Option<String>,
+
+    pub overdraft_limit: Option<String>,
+
 }
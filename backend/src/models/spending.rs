@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SpendingStatus {
+    Pending,
+    Approved,  // Debit posted to the ledger
+    Denied,
+    Cancelled, // Withdrawn by the child
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct SpendingRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: Option<String>,
+    pub bucket_id: Option<Uuid>,
+    pub bucket_name: Option<String>,
+    pub amount: i64,
+    pub description: String,
+    pub status: SpendingStatus,
+    pub note: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub entry_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSpendingSchema {
+    pub amount: i64,
+    pub description: String,
+    pub bucket_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpendingQuery {
+    pub status: Option<SpendingStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewSpendingSchema {
+    pub note: Option<String>,
+}
+
+/// How far a spending request may take a balance below zero
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverdraftPolicy {
+    Block,      // Never below zero
+    Limit(i64), // Down to minus this many cents
+    Allow,      // No limit
+}
+
+impl OverdraftPolicy {
+    /// Read from the `overdraft_policy` and `overdraft_limit` settings, blocking by default
+    pub fn from_settings(policy: Option<&str>, limit: Option<&str>) -> Self {
+        match policy {
+            Some("allow") => OverdraftPolicy::Allow,
+            Some("limit") => OverdraftPolicy::Limit(
+                limit.and_then(|l| l.parse::<i64>().ok()).unwrap_or(0).max(0),
+            ),
+            _ => OverdraftPolicy::Block,
+        }
+    }
+
+    /// Lowest balance the policy permits, if any
+    pub fn floor(&self) -> Option<i64> {
+        match self {
+            OverdraftPolicy::Block => Some(0),
+            OverdraftPolicy::Limit(limit) => Some(-limit),
+            OverdraftPolicy::Allow => None,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SwapStatus {
+    Pending,   // Waiting on the other person
+    Accepted,  // Agreed, waiting on a parent to confirm
+    Completed, // Chores have changed hands
+    Declined,  // Turned down by the other person
+    Rejected,  // Turned down by a parent
+    Cancelled, // Withdrawn by the proposer
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ChoreSwap {
+    pub id: Uuid,
+    pub proposer_id: Uuid,
+    pub proposer_name: Option<String>,
+    pub proposer_chore_id: Uuid,
+    pub proposer_chore_description: Option<String>,
+    pub recipient_id: Uuid,
+    pub recipient_name: Option<String>,
+    pub recipient_chore_id: Uuid,
+    pub recipient_chore_description: Option<String>,
+    pub status: SwapStatus,
+    pub needs_approval: bool,
+    pub note: Option<String>,
+    pub responded_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSwapSchema {
+    pub chore_id: Uuid,
+    pub other_chore_id: Uuid,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SwapQuery {
+    pub status: Option<SwapStatus>,
+}
@@ -0,0 +1,91 @@
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::chore::Recurrence;
+
+/// Age in whole years on the given date
+pub fn age_on(birthday: NaiveDate, date: NaiveDate) -> i64 {
+    let mut age = (date.year() - birthday.year()) as i64;
+    if (date.month(), date.day()) < (birthday.month(), birthday.day()) {
+        age -= 1;
+    }
+    age
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ChoreTemplate {
+    pub id: Uuid,
+    pub description: String,
+    pub reward: Option<i64>,
+    pub points: Option<i64>,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub min_age: Option<i64>,
+    pub max_age: Option<i64>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ChoreTemplate {
+    /// Check if the template is suitable for someone of the given age
+    pub fn suits_age(&self, age: i64) -> bool {
+        self.min_age.is_none_or(|min| age >= min) && self.max_age.is_none_or(|max| age <= max)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTemplateSchema {
+    pub description: String,
+    pub reward: Option<i64>,
+    pub points: Option<i64>,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub min_age: Option<i64>,
+    pub max_age: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTemplateSchema {
+    pub description: Option<String>,
+    pub reward: Option<i64>,
+    pub points: Option<i64>,
+    pub recurrence: Option<Recurrence>,
+    pub recurrence_weekdays: Option<String>,
+    pub recurrence_interval: Option<i64>,
+    pub recurrence_day: Option<i64>,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub window_label: Option<String>,
+    pub min_age: Option<i64>,
+    pub max_age: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTemplatesQuery {
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignTemplateSchema {
+    pub user_ids: Vec<Uuid>,
+    pub due_date: Option<NaiveDate>,
+    pub recurrence_start: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveTemplateSchema {
+    pub min_age: Option<i64>,
+    pub max_age: Option<i64>,
+}
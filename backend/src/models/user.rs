@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::models::allowance::BucketBalance;
+
 #[derive(Debug, Serialize, Deserialize, Clone, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "TEXT", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -63,6 +65,7 @@ pub struct BackupUser {
     pub username: String,
     pub name: String,
     pub password_hash: String,
+    pub pin_hash: Option<String>,
     pub birthday: Option<NaiveDate>,
     pub profile_picture_url: Option<String>,
     pub role: UserRole,
@@ -101,6 +104,11 @@ pub struct ChangePasswordSchema {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangePinSchema {
+    pub pin: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct LoginResponse {
     pub token: String,
@@ -123,12 +131,44 @@ pub struct AllowanceTransaction {
 
     pub description: String,
 
+    pub chore_id: Option<Uuid>,
+
+    pub reversal_of: Option<Uuid>,
+
+    pub voided_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    pub voided_by: Option<Uuid>,
+
+    pub transfer_id: Option<Uuid>,
+
     pub created_at: chrono::DateTime<chrono::Utc>,
 
 }
 
 
 
+#[derive(Debug, Deserialize)]
+
+pub struct VoidTransactionSchema {
+
+    pub reason: Option<String>,
+
+}
+
+
+
+#[derive(Debug, Serialize)]
+
+pub struct VoidedTransaction {
+
+    pub voided: AllowanceTransaction,
+
+    pub reversal: AllowanceTransaction,
+
+}
+
+
+
 #[derive(Debug, Deserialize)]
 
 pub struct CreateTransactionSchema {
@@ -137,6 +177,8 @@ pub struct CreateTransactionSchema {
 
     pub description: String,
 
+    pub bucket_id: Option<Uuid>,
+
 }
 
 
@@ -151,4 +193,10 @@ pub struct UserBalance {
 
     pub balance: Option<i64>,
 
+    #[sqlx(skip)]
+    pub buckets: Vec<BucketBalance>,
+
+    #[sqlx(skip)]
+    pub unallocated: i64,
+
 }
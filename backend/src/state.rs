@@ -2,7 +2,9 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use std::path::PathBuf;
 use chrono::{DateTime, Utc};
+use dashmap::DashMap;
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
 #[derive(Debug, Clone)]
 pub struct CachedPhotos {
@@ -11,6 +13,13 @@ pub struct CachedPhotos {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Failed PIN entries on a display within the current lockout window
+#[derive(Debug, Clone)]
+pub struct PinAttempts {
+    pub failures: u32,
+    pub window_start: DateTime<Utc>,
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub db: SqlitePool,
@@ -23,4 +32,5 @@ pub struct AppState {
     pub google_oauth_redirect_uri: Arc<RwLock<String>>,
     pub base_url: Arc<RwLock<String>>,
     pub photos_dir: PathBuf,
+    pub pin_attempts: Arc<DashMap<Uuid, PinAttempts>>,
 }
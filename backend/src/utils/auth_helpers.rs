@@ -1,4 +1,19 @@
-use crate::{error::AppError, middleware::auth::AuthUser};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    middleware::auth::AuthUser,
+    models::user::UserRole,
+    state::{AppState, PinAttempts},
+    utils::jwt::verify_password,
+};
+
+/// Wrong PINs allowed on one display before it is locked out
+const MAX_PIN_FAILURES: u32 = 5;
+
+/// How long failed PIN entries count against a display
+const PIN_LOCKOUT_MINUTES: i64 = 15;
 
 /// Require the user to be an admin (Owner or Admin role)
 pub fn require_admin(auth: &AuthUser) -> Result<(), AppError> {
@@ -8,6 +23,61 @@ pub fn require_admin(auth: &AuthUser) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Check a user's PIN entered on a display, returning their role.
+///
+/// Wrong PINs count against the display, which is locked out for a while
+/// after too many.
+pub async fn verify_display_pin(
+    state: &AppState,
+    display_id: Uuid,
+    user_id: Uuid,
+    pin: &str,
+) -> Result<UserRole, AppError> {
+    let now = Utc::now();
+    let window = Duration::minutes(PIN_LOCKOUT_MINUTES);
+
+    let (role, pin_hash): (UserRole, Option<String>) = sqlx::query_as(
+        "SELECT role, pin_hash FROM users WHERE id = $1"
+    )
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(AppError::UserNotFound)?;
+
+    let pin_hash = pin_hash
+        .ok_or(AppError::InvalidInput("No PIN set for this user".to_string()))?;
+
+    // Count the attempt as a failure up front, under the entry lock, so
+    // parallel guesses can't all slip in before any failure is recorded.
+    // It's handed back once the PIN turns out to be correct.
+    {
+        let mut attempts = state.pin_attempts.entry(display_id).or_insert(PinAttempts {
+            failures: 0,
+            window_start: now,
+        });
+        if now - attempts.window_start >= window {
+            *attempts = PinAttempts { failures: 0, window_start: now };
+        }
+        if attempts.failures >= MAX_PIN_FAILURES {
+            return Err(AppError::TooManyAttempts);
+        }
+        attempts.failures += 1;
+    }
+
+    if !verify_password(pin, &pin_hash)? {
+        let failures = state.pin_attempts.get(&display_id).map(|a| a.failures).unwrap_or(0);
+        tracing::warn!(%display_id, %user_id, failures, "Incorrect PIN entered on display");
+        return Err(AppError::InvalidInput("Incorrect PIN".to_string()));
+    }
+
+    // Only this attempt is refunded; earlier failures on the display still count
+    if let Some(mut attempts) = state.pin_attempts.get_mut(&display_id) {
+        attempts.failures = attempts.failures.saturating_sub(1);
+    }
+
+    Ok(role)
+}
+
 pub fn generate_random_token(length: usize) -> String {
     use rand::{distr::Alphanumeric, Rng};
     rand::rng()
@@ -0,0 +1,391 @@
+use sqlx::{query_as, SqliteConnection};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    models::{
+        allowance::{split_amount, spread_debit, AllowanceBucket, BucketBalance, LedgerCheck, LedgerMismatch},
+        reward::PointsTransaction,
+        spending::OverdraftPolicy,
+        user::{AllowanceTransaction, UserBalance},
+    },
+    utils::ledger_export::Currency,
+};
+
+/// Largest amount a single entry, reward, charge or request can be, in cents or points
+pub const MAX_REQUEST_AMOUNT: i64 = 100_000_000;
+
+/// An entry to append to a user's allowance ledger
+#[derive(Debug, Default)]
+pub struct NewLedgerEntry {
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub description: String,
+    pub chore_id: Option<Uuid>,
+    pub bucket_id: Option<Uuid>, // put the whole amount in this bucket
+    pub reverses: Option<Uuid>,  // entry this undoes, mirroring its bucket allocations
+    pub transfer_id: Option<Uuid>,
+}
+
+/// Append an entry to the allowance ledger, carrying the running balance forward.
+///
+/// Users with buckets also get the amount allocated to them: into the given
+/// bucket, mirroring the entry being reversed, split by percentage when
+/// incoming, or spread over what the buckets hold when outgoing.
+///
+/// Must be called inside a transaction so the balance read and the insert are atomic.
+pub async fn post_entry(
+    conn: &mut SqliteConnection,
+    entry: NewLedgerEntry,
+) -> Result<AllowanceTransaction, AppError> {
+    let latest_balance: Option<i64> = sqlx::query_scalar(
+        "SELECT balance FROM allowance_ledger WHERE user_id = $1 ORDER BY seq DESC LIMIT 1"
+    )
+        .bind(entry.user_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    let previous_balance = latest_balance.unwrap_or(0);
+    let new_balance = previous_balance
+        .checked_add(entry.amount)
+        .ok_or(AppError::InvalidInput("That would put the balance out of range".to_string()))?;
+    let id = Uuid::new_v4();
+
+    sqlx::query(
+        r#"
+        INSERT INTO allowance_ledger (id, user_id, amount, balance, description, chore_id, reversal_of,
+                                      transfer_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#
+    )
+    .bind(id)
+    .bind(entry.user_id)
+    .bind(entry.amount)
+    .bind(new_balance)
+    .bind(entry.description)
+    .bind(entry.chore_id)
+    .bind(entry.reverses)
+    .bind(entry.transfer_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let allocations: Vec<(Uuid, i64)> = if let Some(bucket_id) = entry.bucket_id {
+        let owned: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM allowance_buckets WHERE id = $1 AND user_id = $2)"
+        )
+            .bind(bucket_id)
+            .bind(entry.user_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        if !owned {
+            return Err(AppError::InvalidInput("Bucket not found".to_string()));
+        }
+
+        vec![(bucket_id, entry.amount)]
+    } else if let Some(original) = entry.reverses {
+        sqlx::query_as("SELECT bucket_id, -amount FROM allowance_allocations WHERE entry_id = $1")
+            .bind(original)
+            .fetch_all(&mut *conn)
+            .await?
+    } else if entry.amount > 0 {
+        split_amount(&user_buckets(&mut *conn, entry.user_id).await?, entry.amount)
+    } else {
+        let buckets = bucket_balances(&mut *conn, entry.user_id).await?;
+        let allocated: i64 = buckets.iter().map(|b| b.balance).sum();
+        spread_debit(&buckets, previous_balance.saturating_sub(allocated), entry.amount)
+    };
+
+    for (bucket_id, amount) in allocations.into_iter().filter(|(_, amount)| *amount != 0) {
+        sqlx::query(
+            "INSERT INTO allowance_allocations (entry_id, bucket_id, amount) VALUES ($1, $2, $3)"
+        )
+        .bind(id)
+        .bind(bucket_id)
+        .bind(amount)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let transaction = query_as::<_, AllowanceTransaction>(
+        "SELECT * FROM allowance_ledger WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(transaction)
+}
+
+/// A user's buckets in order, the first taking rounding and overdrafts
+pub async fn user_buckets(conn: &mut SqliteConnection, user_id: Uuid) -> Result<Vec<AllowanceBucket>, AppError> {
+    let buckets = query_as::<_, AllowanceBucket>(
+        "SELECT * FROM allowance_buckets WHERE user_id = $1 ORDER BY position ASC, created_at ASC"
+    )
+        .bind(user_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    Ok(buckets)
+}
+
+/// Current balance of a user's whole allowance, or of one of their buckets
+pub async fn allowance_balance(
+    conn: &mut SqliteConnection,
+    user_id: Uuid,
+    bucket_id: Option<Uuid>,
+) -> Result<i64, AppError> {
+    let balance: Option<i64> = match bucket_id {
+        Some(bucket_id) => sqlx::query_scalar(
+            r#"
+            SELECT SUM(a.amount) FROM allowance_allocations a
+            JOIN allowance_buckets b ON a.bucket_id = b.id
+            WHERE b.id = $1 AND b.user_id = $2
+            "#
+        )
+            .bind(bucket_id)
+            .bind(user_id)
+            .fetch_one(&mut *conn)
+            .await?,
+        None => sqlx::query_scalar(
+            "SELECT balance FROM allowance_ledger WHERE user_id = $1 ORDER BY seq DESC LIMIT 1"
+        )
+            .bind(user_id)
+            .fetch_optional(&mut *conn)
+            .await?,
+    };
+
+    Ok(balance.unwrap_or(0))
+}
+
+/// The family's overdraft policy from settings
+pub async fn overdraft_policy(conn: &mut SqliteConnection) -> Result<OverdraftPolicy, AppError> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT key, value FROM settings WHERE key IN ('overdraft_policy', 'overdraft_limit')"
+    )
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let value = |key: &str| rows.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+
+    Ok(OverdraftPolicy::from_settings(value("overdraft_policy"), value("overdraft_limit")))
+}
+
+/// Check spending `amount` from a balance (or one bucket) stays within the overdraft policy.
+///
+/// Call inside the transaction that posts the debit so the balance can't change in between.
+pub async fn check_overdraft(
+    conn: &mut SqliteConnection,
+    user_id: Uuid,
+    bucket_id: Option<Uuid>,
+    amount: i64,
+) -> Result<(), AppError> {
+    let policy = overdraft_policy(&mut *conn).await?;
+    let Some(floor) = policy.floor() else {
+        return Ok(());
+    };
+
+    let balance = allowance_balance(&mut *conn, user_id, bucket_id).await?;
+    // An amount too large to subtract is as overdrawn as it gets
+    if balance.checked_sub(amount).is_none_or(|left| left < floor) {
+        let message = match policy {
+            OverdraftPolicy::Limit(limit) => {
+                let currency = Currency::load(&mut *conn).await?;
+                format!("That would go more than {} {} overdrawn", currency.format(limit), currency.code)
+            }
+            _ => "Not enough money".to_string(),
+        };
+        return Err(AppError::InvalidInput(message));
+    }
+
+    Ok(())
+}
+
+/// Current balance of each of a user's buckets
+pub async fn bucket_balances(conn: &mut SqliteConnection, user_id: Uuid) -> Result<Vec<BucketBalance>, AppError> {
+    let balances = query_as::<_, BucketBalance>(
+        r#"
+        SELECT b.id as bucket_id, b.name, b.split_percent, b.position,
+               COALESCE(SUM(a.amount), 0) as balance
+        FROM allowance_buckets b
+        LEFT JOIN allowance_allocations a ON a.bucket_id = b.id
+        WHERE b.user_id = $1
+        GROUP BY b.id
+        ORDER BY b.position ASC, b.created_at ASC
+        "#
+    )
+        .bind(user_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    Ok(balances)
+}
+
+/// Fill in the per-bucket breakdown of each balance
+pub async fn with_buckets(conn: &mut SqliteConnection, balances: Vec<UserBalance>) -> Result<Vec<UserBalance>, AppError> {
+    let mut result = Vec::with_capacity(balances.len());
+
+    for mut balance in balances {
+        balance.buckets = bucket_balances(&mut *conn, balance.user_id).await?;
+        balance.unallocated = balance.balance.unwrap_or(0) - balance.buckets.iter().map(|b| b.balance).sum::<i64>();
+        result.push(balance);
+    }
+
+    Ok(result)
+}
+
+/// Move money between two of a user's buckets.
+///
+/// Recorded as a zero-amount ledger entry so the move shows up in history
+/// without changing the user's total. A missing source bucket takes the
+/// money from the unallocated balance.
+pub async fn transfer_between_buckets(
+    conn: &mut SqliteConnection,
+    user_id: Uuid,
+    from_bucket_id: Option<Uuid>,
+    to_bucket_id: Uuid,
+    amount: i64,
+    description: String,
+) -> Result<AllowanceTransaction, AppError> {
+    let transaction = post_entry(&mut *conn, NewLedgerEntry {
+        user_id,
+        amount: 0,
+        description,
+        ..Default::default()
+    }).await?;
+
+    let moves = from_bucket_id
+        .map(|from| (from, -amount))
+        .into_iter()
+        .chain([(to_bucket_id, amount)]);
+
+    for (bucket_id, amount) in moves {
+        sqlx::query(
+            "INSERT INTO allowance_allocations (entry_id, bucket_id, amount) VALUES ($1, $2, $3)"
+        )
+        .bind(transaction.id)
+        .bind(bucket_id)
+        .bind(amount)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(transaction)
+}
+
+/// Recompute every running balance from the amounts in `seq` order.
+///
+/// Reports each stored balance that disagrees, plus entries whose timestamps
+/// run backwards (restored backups do this) since anything ordering by time
+/// instead of `seq` would pick the wrong latest balance. With `repair` the
+/// stored balances are rewritten; run it inside a transaction.
+pub async fn verify_ledger(conn: &mut SqliteConnection, repair: bool) -> Result<LedgerCheck, AppError> {
+    let entries: Vec<(Uuid, i64, Uuid, i64, i64, chrono::DateTime<chrono::Utc>)> = sqlx::query_as(
+        "SELECT user_id, seq, id, amount, balance, created_at FROM allowance_ledger ORDER BY user_id, seq"
+    )
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let mut users_checked = 0;
+    let mut mismatches = Vec::new();
+    let mut out_of_order = Vec::new();
+    let mut previous: Option<(Uuid, i64, chrono::DateTime<chrono::Utc>)> = None;
+
+    for (user_id, seq, entry_id, amount, stored_balance, created_at) in entries.iter().copied() {
+        let (running, last_created) = match previous {
+            Some((prev_user, balance, created)) if prev_user == user_id => (balance, Some(created)),
+            _ => {
+                users_checked += 1;
+                (0, None)
+            }
+        };
+
+        if last_created.is_some_and(|last| created_at < last) {
+            out_of_order.push(entry_id);
+        }
+
+        let expected_balance = running + amount;
+        if stored_balance != expected_balance {
+            mismatches.push(LedgerMismatch { user_id, seq, entry_id, stored_balance, expected_balance });
+        }
+
+        previous = Some((user_id, expected_balance, created_at));
+    }
+
+    if repair {
+        for mismatch in &mismatches {
+            sqlx::query("UPDATE allowance_ledger SET balance = $1 WHERE id = $2")
+                .bind(mismatch.expected_balance)
+                .bind(mismatch.entry_id)
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+
+    Ok(LedgerCheck {
+        users_checked,
+        entries_checked: entries.len() as i64,
+        repaired: repair && !mismatches.is_empty(),
+        mismatches,
+        out_of_order,
+    })
+}
+
+/// An entry to append to a user's points ledger
+#[derive(Debug, Default)]
+pub struct NewPointsEntry {
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub description: String,
+    pub chore_id: Option<Uuid>,
+}
+
+/// Current points balance for a user
+pub async fn points_balance(conn: &mut SqliteConnection, user_id: Uuid) -> Result<i64, AppError> {
+    let balance: Option<i64> = sqlx::query_scalar(
+        "SELECT balance FROM points_ledger WHERE user_id = $1 ORDER BY seq DESC LIMIT 1"
+    )
+        .bind(user_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    Ok(balance.unwrap_or(0))
+}
+
+/// Append an entry to the points ledger, carrying the running balance forward.
+///
+/// Must be called inside a transaction so the balance read and the insert are atomic.
+pub async fn post_points_entry(
+    conn: &mut SqliteConnection,
+    entry: NewPointsEntry,
+) -> Result<PointsTransaction, AppError> {
+    let new_balance = points_balance(&mut *conn, entry.user_id)
+        .await?
+        .checked_add(entry.amount)
+        .ok_or(AppError::InvalidInput("That would put the points balance out of range".to_string()))?;
+    let id = Uuid::new_v4();
+
+    sqlx::query(
+        r#"
+        INSERT INTO points_ledger (id, user_id, amount, balance, description, chore_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#
+    )
+    .bind(id)
+    .bind(entry.user_id)
+    .bind(entry.amount)
+    .bind(new_balance)
+    .bind(entry.description)
+    .bind(entry.chore_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let transaction = query_as::<_, PointsTransaction>(
+        "SELECT * FROM points_ledger WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(transaction)
+}
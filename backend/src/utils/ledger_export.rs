@@ -0,0 +1,172 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::SqliteConnection;
+
+use crate::{error::AppError, models::user::AllowanceTransaction};
+
+pub const DEFAULT_CURRENCY_CODE: &str = "USD";
+pub const DEFAULT_CURRENCY_DECIMALS: u32 = 2;
+
+/// How the family's amounts are written out, from the currency settings
+#[derive(Debug, Clone)]
+pub struct Currency {
+    pub code: String,
+    pub decimals: u32,
+}
+
+impl Currency {
+    pub async fn load(conn: &mut SqliteConnection) -> Result<Self, AppError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT key, value FROM settings WHERE key IN ('currency_code', 'currency_decimals')"
+        )
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let mut currency = Currency {
+            code: DEFAULT_CURRENCY_CODE.to_string(),
+            decimals: DEFAULT_CURRENCY_DECIMALS,
+        };
+
+        for (key, value) in rows {
+            match key.as_str() {
+                "currency_code" if !value.is_empty() => currency.code = value,
+                "currency_decimals" => {
+                    if let Ok(decimals) = value.parse::<u32>() {
+                        currency.decimals = decimals.min(3);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(currency)
+    }
+
+    /// Stored minor units (cents) as a plain decimal, e.g. -150 -> "-1.50"
+    pub fn format(&self, amount: i64) -> String {
+        Decimal::new(amount, self.decimals).to_string()
+    }
+}
+
+fn local_time(time: DateTime<Utc>) -> DateTime<Local> {
+    time.with_timezone(&Local)
+}
+
+/// Quoted when needed, and text a spreadsheet would run as a formula is prefixed with `'`
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Comma separated with a header row, oldest first
+pub fn to_csv(entries: &[AllowanceTransaction], currency: &Currency) -> String {
+    let mut out = String::from("Date,Description,Amount,Balance,Currency,Voided,Id\r\n");
+
+    for entry in entries {
+        let row = [
+            local_time(entry.created_at).format("%Y-%m-%d %H:%M:%S").to_string(),
+            csv_field(&entry.description),
+            currency.format(entry.amount),
+            currency.format(entry.balance),
+            currency.code.clone(),
+            entry.voided_at.map(|t| local_time(t).format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            entry.id.to_string(),
+        ];
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+fn sgml_text(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace(['\r', '\n'], " ")
+}
+
+/// OFX 1.0.2 bank statement, the flavour most budgeting apps import.
+///
+/// Zero-amount entries (moves between buckets) are left out since they
+/// aren't transactions as far as a bank statement is concerned.
+pub fn to_ofx(
+    entries: &[AllowanceTransaction],
+    currency: &Currency,
+    account_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    closing_balance: i64,
+) -> String {
+    let now = Local::now().format("%Y%m%d%H%M%S");
+    let mut out = String::new();
+
+    out.push_str(
+        "OFXHEADER:100\r\nDATA:OFXSGML\r\nVERSION:102\r\nSECURITY:NONE\r\nENCODING:UNICODE\r\n\
+         CHARSET:NONE\r\nCOMPRESSION:NONE\r\nOLDFILEUID:NONE\r\nNEWFILEUID:NONE\r\n\r\n",
+    );
+    out.push_str("<OFX>\r\n<SIGNONMSGSRSV1>\r\n<SONRS>\r\n");
+    out.push_str("<STATUS>\r\n<CODE>0\r\n<SEVERITY>INFO\r\n</STATUS>\r\n");
+    out.push_str(&format!("<DTSERVER>{}\r\n<LANGUAGE>ENG\r\n", now));
+    out.push_str("</SONRS>\r\n</SIGNONMSGSRSV1>\r\n");
+    out.push_str("<BANKMSGSRSV1>\r\n<STMTTRNRS>\r\n<TRNUID>0\r\n");
+    out.push_str("<STATUS>\r\n<CODE>0\r\n<SEVERITY>INFO\r\n</STATUS>\r\n");
+    out.push_str(&format!("<STMTRS>\r\n<CURDEF>{}\r\n", currency.code));
+    out.push_str(&format!(
+        "<BANKACCTFROM>\r\n<BANKID>HOME\r\n<ACCTID>{}\r\n<ACCTTYPE>SAVINGS\r\n</BANKACCTFROM>\r\n",
+        account_id
+    ));
+    out.push_str(&format!(
+        "<BANKTRANLIST>\r\n<DTSTART>{}\r\n<DTEND>{}\r\n",
+        from.format("%Y%m%d"),
+        to.format("%Y%m%d")
+    ));
+
+    for entry in entries.iter().filter(|e| e.amount != 0) {
+        let name: String = entry.description.chars().take(32).collect();
+        out.push_str("<STMTTRN>\r\n");
+        out.push_str(&format!("<TRNTYPE>{}\r\n", if entry.amount > 0 { "CREDIT" } else { "DEBIT" }));
+        out.push_str(&format!("<DTPOSTED>{}\r\n", local_time(entry.created_at).format("%Y%m%d%H%M%S")));
+        out.push_str(&format!("<TRNAMT>{}\r\n", currency.format(entry.amount)));
+        out.push_str(&format!("<FITID>{}\r\n", entry.id.simple()));
+        out.push_str(&format!("<NAME>{}\r\n", sgml_text(&name)));
+        out.push_str(&format!("<MEMO>{}\r\n", sgml_text(&entry.description)));
+        out.push_str("</STMTTRN>\r\n");
+    }
+
+    out.push_str("</BANKTRANLIST>\r\n");
+    out.push_str(&format!(
+        "<LEDGERBAL>\r\n<BALAMT>{}\r\n<DTASOF>{}\r\n</LEDGERBAL>\r\n",
+        currency.format(closing_balance),
+        to.format("%Y%m%d")
+    ));
+    out.push_str("</STMTRS>\r\n</STMTTRNRS>\r\n</BANKMSGSRSV1>\r\n</OFX>\r\n");
+
+    out
+}
+
+/// Quicken interchange format as a bank account, zero-amount entries left out
+pub fn to_qif(entries: &[AllowanceTransaction], currency: &Currency) -> String {
+    let mut out = String::from("!Type:Bank\n");
+
+    for entry in entries.iter().filter(|e| e.amount != 0) {
+        let description = entry.description.replace(['\r', '\n'], " ");
+        out.push_str(&format!("D{}\n", local_time(entry.created_at).format("%m/%d/%Y")));
+        out.push_str(&format!("T{}\n", currency.format(entry.amount)));
+        out.push_str(&format!("P{}\n", description));
+        out.push_str(&format!("N{}\n", entry.seq));
+        out.push_str("^\n");
+    }
+
+    out
+}
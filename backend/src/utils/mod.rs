@@ -1,4 +1,7 @@
 pub mod jwt;
 pub mod google_photos;
 pub mod google_oauth;
-pub mod auth_helpers;
\ No newline at end of file
+pub mod auth_helpers;
+pub mod ledger;
+pub mod photo_files;
+pub mod ledger_export;
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::AppError;
+
+/// Subfolder of the photos directory holding chore proof photos
+pub const CHORE_PROOFS_DIR: &str = "chore_proofs";
+
+/// How long chore proof photos are kept when no retention is configured
+pub const DEFAULT_PROOF_RETENTION_DAYS: i64 = 30;
+
+/// Resolve a stored photo's filename inside `dir`, rejecting anything that could escape it
+pub fn photo_path(dir: &Path, filename: &str) -> Result<PathBuf, AppError> {
+    // Security check for filename to prevent path traversal
+    // Only allow alphanumeric IDs with .jpg or .png extension
+    static FILENAME_REGEX: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let re = FILENAME_REGEX.get_or_init(|| regex::Regex::new(r"^[a-zA-Z0-9\-_]+\.(jpg|jpeg|png)$").unwrap());
+
+    if !re.is_match(filename) {
+        return Err(AppError::BadRequest("Invalid filename".to_string()));
+    }
+
+    // Ensure the filename itself doesn't have any path components
+    if Path::new(filename).components().count() != 1 {
+        return Err(AppError::BadRequest("Invalid filename structure".to_string()));
+    }
+
+    let file_path = dir.join(filename);
+
+    // Canonicalize paths to ensure no symbolic link tricks or traversal
+    // However, since we strictly regex the filename, join() is safe.
+    // To be extra sure, we check that the resulting path starts with the photos directory.
+    if !file_path.starts_with(dir) {
+        return Err(AppError::BadRequest("Invalid path".to_string()));
+    }
+
+    Ok(file_path)
+}
+
+/// Content type to serve a stored photo with, based on its extension
+pub fn photo_content_type(filename: &str) -> &'static str {
+    if filename.to_lowercase().ends_with(".png") {
+        "image/png"
+    } else {
+        "image/jpeg"
+    }
+}
+
+/// File extension for an uploaded image, judged by its contents rather than the client's claim
+pub fn image_extension(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else {
+        None
+    }
+}
@@ -1,5 +1,5 @@
 import type { AxiosInstance } from 'axios';
-import type { AllowanceTransaction, UserBalance } from '../types';
+import type { AllowanceTransaction, LedgerPage, UserBalance } from '../types';
 
 export const createAllowanceApi = (client: AxiosInstance) => ({
   getBalances: async (): Promise<UserBalance[]> => {
@@ -7,8 +7,10 @@ export const createAllowanceApi = (client: AxiosInstance) => ({
     return response.data;
   },
 
-  getLedger: async (userId: string): Promise<AllowanceTransaction[]> => {
-    const response = await client.get<AllowanceTransaction[]>(`/allowance/${userId}`);
+  getLedgerPage: async (userId: string, before: number | null = null): Promise<LedgerPage> => {
+    // Newest first; pass the previous page's next_cursor to get the one after it
+    const params = before === null ? {} : { before };
+    const response = await client.get<LedgerPage>(`/allowance/${userId}/ledger`, { params });
     return response.data;
   },
 
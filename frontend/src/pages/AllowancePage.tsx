@@ -21,7 +21,7 @@ import {
   CardContent
 } from '@mui/material';
 import { Add as AddIcon, History as HistoryIcon } from '@mui/icons-material';
-import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
+import { useQuery, useInfiniteQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { allowanceApi } from '../api';
 import type { UserBalance } from '../types';
 import { useAuth } from '../context/AuthContext';
@@ -42,17 +42,29 @@ export default function AllowancePage() {
   });
 
   const { data: myLedger, isLoading: isLoadingMyLedger } = useQuery({
-    queryKey: ['ledger', userId],
-    queryFn: () => allowanceApi.getLedger(userId!),
+    queryKey: ['ledger', userId, 'recent'],
+    queryFn: () => allowanceApi.getLedgerPage(userId!),
     enabled: !isAdmin && !!userId,
   });
 
-  const { data: ledger, isLoading: isLoadingLedger } = useQuery({
-    queryKey: ['ledger', viewingLedger],
-    queryFn: () => allowanceApi.getLedger(viewingLedger!),
+  const {
+    data: ledgerPages,
+    isLoading: isLoadingLedger,
+    fetchNextPage,
+    hasNextPage,
+    isFetchingNextPage,
+  } = useInfiniteQuery({
+    queryKey: ['ledger', viewingLedger, 'pages'],
+    queryFn: ({ pageParam }) => allowanceApi.getLedgerPage(viewingLedger!, pageParam),
+    initialPageParam: null as number | null,
+    getNextPageParam: (lastPage) => lastPage.next_cursor,
     enabled: isAdmin && !!viewingLedger,
   });
 
+  const ledger = ledgerPages?.pages.flatMap((page) => page.entries);
+  // Totals cover the whole ledger, not just the pages loaded so far
+  const ledgerSummary = ledgerPages?.pages[0]?.summary;
+
   const addTxMutation = useMutation({
     mutationFn: ({ userId, amount, description }: { userId: string, amount: number, description: string }) =>
       allowanceApi.addTransaction(userId, amount, description),
@@ -98,7 +110,7 @@ export default function AllowancePage() {
   // Non-admin view - show only their balance and recent transactions
   if (!isAdmin) {
     const myBalance = balances?.find(b => b.user_id === userId);
-    const recentTransactions = myLedger?.slice(0, 10) || [];
+    const recentTransactions = myLedger?.entries.slice(0, 10) || [];
 
     return (
       <Box>
@@ -221,36 +233,51 @@ export default function AllowancePage() {
                   <Typography variant="h6">Transaction History</Typography>
                   <Button size="small" onClick={() => setViewingLedger(null)}>Close</Button>
                 </Box>
+                {ledgerSummary && (
+                  <Typography variant="body2" color="text.secondary" mb={1}>
+                    {ledgerSummary.total_count} transactions · In {formatCurrency(ledgerSummary.credits)} ·
+                    Out {formatCurrency(-ledgerSummary.debits)} · Net {formatCurrency(ledgerSummary.net)}
+                  </Typography>
+                )}
                 {isLoadingLedger ? (
                   <CircularProgress size={20} />
                 ) : (
-                  <Table size="small">
-                    <TableHead>
-                      <TableRow>
-                        <TableCell>Date</TableCell>
-                        <TableCell>Description</TableCell>
-                        <TableCell align="right">Amount</TableCell>
-                        <TableCell align="right">Balance</TableCell>
-                      </TableRow>
-                    </TableHead>
-                    <TableBody>
-                      {ledger?.map((tx) => (
-                        <TableRow key={tx.id}>
-                          <TableCell>{new Date(tx.created_at).toLocaleDateString()}</TableCell>
-                          <TableCell>{tx.description}</TableCell>
-                          <TableCell align="right" sx={{ color: tx.amount < 0 ? 'error.main' : 'success.main' }}>
-                            {formatCurrency(tx.amount)}
-                          </TableCell>
-                          <TableCell align="right">{formatCurrency(tx.balance)}</TableCell>
-                        </TableRow>
-                      ))}
-                      {ledger?.length === 0 && (
+                  <>
+                    <Table size="small">
+                      <TableHead>
                         <TableRow>
-                          <TableCell colSpan={4} align="center">No transactions yet.</TableCell>
+                          <TableCell>Date</TableCell>
+                          <TableCell>Description</TableCell>
+                          <TableCell align="right">Amount</TableCell>
+                          <TableCell align="right">Balance</TableCell>
                         </TableRow>
-                      )}
-                    </TableBody>
-                  </Table>
+                      </TableHead>
+                      <TableBody>
+                        {ledger?.map((tx) => (
+                          <TableRow key={tx.id}>
+                            <TableCell>{new Date(tx.created_at).toLocaleDateString()}</TableCell>
+                            <TableCell>{tx.description}</TableCell>
+                            <TableCell align="right" sx={{ color: tx.amount < 0 ? 'error.main' : 'success.main' }}>
+                              {formatCurrency(tx.amount)}
+                            </TableCell>
+                            <TableCell align="right">{formatCurrency(tx.balance)}</TableCell>
+                          </TableRow>
+                        ))}
+                        {ledger?.length === 0 && (
+                          <TableRow>
+                            <TableCell colSpan={4} align="center">No transactions yet.</TableCell>
+                          </TableRow>
+                        )}
+                      </TableBody>
+                    </Table>
+                    {hasNextPage && (
+                      <Box display="flex" justifyContent="center" mt={1}>
+                        <Button size="small" onClick={() => fetchNextPage()} disabled={isFetchingNextPage}>
+                          {isFetchingNextPage ? 'Loading...' : 'Load more'}
+                        </Button>
+                      </Box>
+                    )}
+                  </>
                 )}
              </Paper>
           </Grid>
@@ -38,6 +38,17 @@ export interface AllowanceTransaction {
   created_at: string;
 }
 
+export interface LedgerPage {
+  entries: AllowanceTransaction[];
+  next_cursor: number | null;
+  summary: {
+    total_count: number;
+    credits: number;
+    debits: number;
+    net: number;
+  };
+}
+
 export interface UserBalance {
   user_id: string;
   name: string;